@@ -0,0 +1,123 @@
+use std::io::Read;
+use std::process::{Child, ChildStderr, ChildStdout};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Output kept per stream while reading | 스트림별 보관 크기
+const TAIL_BYTES: usize = 4096;
+// Lines written to the log per stream | 로그에 기록할 줄 수
+const TAIL_LINES: usize = 10;
+
+// Capture Report | 실행 결과 보고
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptureReport {
+    pub label: String,
+    pub pid: u32,
+    pub exit_code: Option<i32>, // None if terminated without a code
+    pub duration: Duration,
+    pub stdout_tail: String,
+    pub stderr_tail: String,
+}
+
+impl CaptureReport {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+// Function: Keep Last Lines | 마지막 줄만 남기기
+pub fn tail_lines(text: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    lines[start..].join("\n")
+}
+
+// Function: Read Stream Tail | 스트림 끝부분 읽기
+// Keeps memory bounded for chatty commands
+fn read_tail<R: Read>(mut reader: R) -> String {
+    let mut tail: Vec<u8> = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                tail.extend_from_slice(&buf[..n]);
+                if tail.len() > TAIL_BYTES * 2 {
+                    tail.drain(..tail.len() - TAIL_BYTES);
+                }
+            }
+        }
+    }
+    if tail.len() > TAIL_BYTES {
+        tail.drain(..tail.len() - TAIL_BYTES);
+    }
+    tail_lines(&String::from_utf8_lossy(&tail), TAIL_LINES)
+}
+
+fn spawn_reader<R: Read + Send + 'static>(stream: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || stream.map(read_tail).unwrap_or_default())
+}
+
+// Function: Watch Captured Child | 자식 프로세스 대기
+// Waits in a background thread and sends the report back to the event loop
+pub fn watch(label: String, mut child: Child, tx: Sender<CaptureReport>) {
+    let started = Instant::now();
+    let pid = child.id();
+    let stdout: Option<ChildStdout> = child.stdout.take();
+    let stderr: Option<ChildStderr> = child.stderr.take();
+
+    thread::spawn(move || {
+        let stdout_reader = spawn_reader(stdout);
+        let stderr_reader = spawn_reader(stderr);
+
+        let exit_code = child.wait().ok().and_then(|status| status.code());
+
+        let report = CaptureReport {
+            label,
+            pid,
+            exit_code,
+            duration: started.elapsed(),
+            stdout_tail: stdout_reader.join().unwrap_or_default(),
+            stderr_tail: stderr_reader.join().unwrap_or_default(),
+        };
+        let _ = tx.send(report);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_lines_ignores_trailing_newline() {
+        assert_eq!(tail_lines("one\ntwo\nthree\n", 2), "two\nthree");
+        assert_eq!(tail_lines("one\n\n\n", 5), "one");
+    }
+
+    #[test]
+    fn tail_lines_strips_crlf() {
+        assert_eq!(tail_lines("one\r\ntwo\r\nthree\r\n", 2), "two\nthree");
+    }
+
+    #[test]
+    fn tail_lines_with_fewer_lines_than_requested() {
+        assert_eq!(tail_lines("only\n", 10), "only");
+        assert_eq!(tail_lines("", 3), "");
+    }
+
+    #[test]
+    fn read_tail_keeps_last_bytes_of_long_output() {
+        let output: String = (0..50).map(|i| format!("{:03}{}\n", i, "x".repeat(996))).collect();
+        assert!(output.len() > TAIL_BYTES * 2);
+        let tail = read_tail(output.as_bytes());
+        assert!(tail.len() <= TAIL_BYTES);
+        assert!(tail.ends_with(&format!("049{}", "x".repeat(996))));
+        assert!(!tail.contains("045"));
+    }
+
+    #[test]
+    fn read_tail_keeps_short_output() {
+        assert_eq!(read_tail("a\r\nb\r\n".as_bytes()), "a\nb");
+    }
+}
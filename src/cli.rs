@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use crate::ipc::IpcCommand;
+use crate::localization::Message;
+
+// Command-line Arguments | 명령줄 인자
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    pub profile: Option<String>, // --profile <name>
+    pub config: Option<PathBuf>, // --config <path>
+    pub command: Option<IpcCommand>, // --show, --reload, --run <id>, --exit
+    pub subcommand: Option<Subcommand>, // Headless: validate, list, run, dump
+    pub dry_run: bool, // --dry-run: log launches instead of running them
+}
+
+// Headless Subcommand | 창 없이 실행하는 하위 명령
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Subcommand {
+    Validate(Option<PathBuf>), // validate [<ini>]
+    List,                      // list
+    Run(String),               // run <label|id>
+    Dump,                      // dump --json
+}
+
+// Function: Parse Arguments | 명령줄 인자 파싱
+// Accepts both "--name value" and "--name=value". `args` excludes the program name.
+// Errors are localized by the caller once the language is known.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<CliArgs, Message> {
+    let mut parsed = CliArgs::default();
+    let mut positionals: Vec<String> = Vec::new();
+    let mut json = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                (name.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| Message::new("cli_missing_value", &[&name]))
+        };
+        match name.as_str() {
+            "--profile" => parsed.profile = Some(value()?),
+            "--config" => parsed.config = Some(PathBuf::from(value()?)),
+            "--show" => parsed.command = Some(IpcCommand::Show),
+            "--reload" => parsed.command = Some(IpcCommand::Reload),
+            "--run" => parsed.command = Some(IpcCommand::Run(value()?)),
+            "--exit" => parsed.command = Some(IpcCommand::Exit),
+            "--json" => json = true,
+            "--dry-run" => parsed.dry_run = true,
+            _ if !arg.starts_with("--") => positionals.push(arg.clone()),
+            _ => return Err(Message::new("cli_unknown_argument", &[&arg])),
+        }
+    }
+    parsed.subcommand = parse_subcommand(&positionals, json)?;
+    Ok(parsed)
+}
+
+// Function: Parse Subcommand | 하위 명령 파싱
+fn parse_subcommand(positionals: &[String], json: bool) -> Result<Option<Subcommand>, Message> {
+    let Some((name, rest)) = positionals.split_first() else {
+        return match json {
+            true => Err(Message::new("cli_json_needs_dump", &[])),
+            false => Ok(None),
+        };
+    };
+    let subcommand = match (name.as_str(), rest) {
+        ("validate", []) => Subcommand::Validate(None),
+        ("validate", [path]) => Subcommand::Validate(Some(PathBuf::from(path))),
+        ("list", []) => Subcommand::List,
+        // Unquoted labels with spaces arrive as several arguments
+        ("run", [_, ..]) => Subcommand::Run(rest.join(" ")),
+        ("run", []) => return Err(Message::new("cli_run_needs_item", &[])),
+        ("dump", []) if json => Subcommand::Dump,
+        ("dump", []) => return Err(Message::new("cli_dump_needs_json", &[])),
+        ("validate" | "list" | "dump", _) => {
+            return Err(Message::new("cli_too_many_arguments", &[name]))
+        }
+        _ => return Err(Message::new("cli_unknown_command", &[name])),
+    };
+    if json && subcommand != Subcommand::Dump {
+        return Err(Message::new("cli_json_needs_dump", &[]));
+    }
+    Ok(Some(subcommand))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::localization::KEYS;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, Message> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_options_and_subcommands() {
+        let args = parse(&["--profile=work", "--dry-run", "run", "Dev", "Tools"]).unwrap();
+        assert_eq!(args.profile.as_deref(), Some("work"));
+        assert!(args.dry_run);
+        assert_eq!(args.subcommand, Some(Subcommand::Run("Dev Tools".to_string())));
+        assert_eq!(parse(&["dump", "--json"]).unwrap().subcommand, Some(Subcommand::Dump));
+    }
+
+    #[test]
+    fn errors_carry_localization_keys() {
+        let cases = [
+            (vec!["--profile"], "cli_missing_value"),
+            (vec!["--bogus"], "cli_unknown_argument"),
+            (vec!["frobnicate"], "cli_unknown_command"),
+            (vec!["list", "extra"], "cli_too_many_arguments"),
+            (vec!["run"], "cli_run_needs_item"),
+            (vec!["dump"], "cli_dump_needs_json"),
+            (vec!["--json"], "cli_json_needs_dump"),
+            (vec!["list", "--json"], "cli_json_needs_dump"),
+        ];
+        for (args, key) in cases {
+            let err = parse(&args).unwrap_err();
+            assert_eq!(err.key, key, "{:?}", args);
+            assert!(KEYS.contains(&err.key));
+        }
+        assert_eq!(parse(&["--bogus"]).unwrap_err().args, vec!["--bogus"]);
+    }
+}
@@ -0,0 +1,597 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::dispatch::{TrayAction, TrayClicks};
+use crate::history::HistorySettings;
+use crate::localization::{fill, LocalizedStrings};
+use crate::logging::{LogFormat, LogLevel, LogSettings};
+use crate::notify::Severity;
+
+// Per-item section prefix, e.g. [item:CMD] | 항목별 섹션 접두어
+const ITEM_SECTION_PREFIX: &str = "item:";
+// Submenu section prefix, e.g. [apps/Dev Tools] | 하위 메뉴 섹션 접두어
+const APPS_SUBMENU_PREFIX: &str = "apps/";
+
+// Item Options | 항목별 옵션
+// Set in a [item:<Label>] section below [apps]. Nested items use [item:<Submenu>/<Label>].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ItemOptions {
+    pub id: Option<String>, // Explicit stable ID that survives label renames
+    pub elevate: bool, // Run as administrator (ShellExecute "runas")
+    pub capture: bool, // Wait for exit and log exit code and output
+    pub single: bool,  // Focus an existing window instead of launching again
+    pub match_exe: Option<String>, // Image name pattern for `single`, e.g. chrome.exe
+    pub match_title: Option<String>, // Window title pattern for `single`, e.g. *Obsidian*
+    pub icon: Option<String>, // Menu icon: .png, .ico, or .exe/.dll with optional ",index"
+    pub labels: BTreeMap<String, String>, // `label.<lang>=`, keyed by lowercase locale
+}
+
+impl ItemOptions {
+    // Apply one `key=value` pair. Returns false for unknown keys or invalid values.
+    fn apply(&mut self, key: &str, value: &str) -> bool {
+        let flag = match key.to_lowercase().as_str() {
+            "elevate" => &mut self.elevate,
+            "capture" => &mut self.capture,
+            "single" => &mut self.single,
+            "id" => {
+                self.id = Some(value.to_string());
+                return true;
+            }
+            "match_exe" => {
+                self.match_exe = Some(value.to_string());
+                return true;
+            }
+            "match_title" => {
+                self.match_title = Some(value.to_string());
+                return true;
+            }
+            "icon" => {
+                self.icon = Some(value.to_string());
+                return true;
+            }
+            other => match other.strip_prefix("label.") {
+                Some(lang) if !lang.is_empty() => {
+                    self.labels
+                        .insert(lang.replace('_', "-"), value.to_string());
+                    return true;
+                }
+                _ => return false,
+            },
+        };
+        match parse_bool(value) {
+            Some(b) => {
+                *flag = b;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Menu Item from [apps] | [apps] 메뉴 항목
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppEntry {
+    // Stable ID: explicit `id=`, otherwise the qualified label (e.g. "Dev Tools/Git").
+    // Used by history and anything that refers to an item across reloads.
+    pub id: String,
+    pub path: Vec<String>, // Submenu path from [apps/<A>/<B>], empty for [apps]
+    pub label: String,
+    pub command: String,
+    pub options: ItemOptions,
+    pub line: usize,
+}
+
+impl AppEntry {
+    // Submenu path and label joined by '/', as used in [item:<...>]
+    pub fn qualified_label(&self) -> String {
+        let mut parts = self.path.clone();
+        parts.push(self.label.clone());
+        parts.join("/")
+    }
+
+    // Label for `locale` from `label.<lang>=`, trying parent languages ("pt-br" -> "pt")
+    // before the label in [apps]
+    pub fn label_for(&self, locale: &str) -> &str {
+        crate::localization::parent_chain(locale)
+            .iter()
+            .find_map(|lang| self.options.labels.get(lang))
+            .unwrap_or(&self.label)
+    }
+
+    // `qualified_label` with the localized label, for display only
+    pub fn qualified_label_for(&self, locale: &str) -> String {
+        let mut parts = self.path.clone();
+        parts.push(self.label_for(locale).to_string());
+        parts.join("/")
+    }
+}
+
+// Built-in Menu Item | 기본 제공 메뉴 항목
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    Edit,
+    Reload,
+    OpenLogs,
+    OpenConfigFolder,
+    About,
+    Exit,
+}
+
+// Shown when [global] builtins= is not set
+pub const DEFAULT_BUILTINS: [Builtin; 3] = [Builtin::Edit, Builtin::Reload, Builtin::Exit];
+
+impl Builtin {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "edit" => Some(Builtin::Edit),
+            "reload" => Some(Builtin::Reload),
+            "logs" => Some(Builtin::OpenLogs),
+            "config_folder" => Some(Builtin::OpenConfigFolder),
+            "about" => Some(Builtin::About),
+            "exit" => Some(Builtin::Exit),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Builtin::Edit => "edit",
+            Builtin::Reload => "reload",
+            Builtin::OpenLogs => "logs",
+            Builtin::OpenConfigFolder => "config_folder",
+            Builtin::About => "about",
+            Builtin::Exit => "exit",
+        }
+    }
+}
+
+// Built-in Items Layout from [global] | 기본 제공 메뉴 배치
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltinMenu {
+    pub items: Vec<Builtin>, // In menu order; empty hides them all
+    pub submenu: bool,       // Group them in a "QikMenu" submenu
+    pub top: bool,           // Above the apps instead of at the bottom
+}
+
+impl Default for BuiltinMenu {
+    fn default() -> Self {
+        Self {
+            items: DEFAULT_BUILTINS.to_vec(),
+            submenu: false,
+            top: false,
+        }
+    }
+}
+
+// Config Problem with Line Number | 환경 설정 문제 (줄 번호 포함)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,       // 1-based
+    pub key: &'static str, // Localization key of the message
+    pub args: Vec<String>, // Fills {0}, {1}, ... in the message
+}
+
+impl Diagnostic {
+    pub fn new(line: usize, key: &'static str, args: &[&str]) -> Self {
+        Self {
+            line,
+            key,
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    // Message in the active language
+    pub fn message(&self, strings: &LocalizedStrings) -> String {
+        let args: Vec<&str> = self.args.iter().map(String::as_str).collect();
+        fill(strings.get(self.key), &args)
+    }
+}
+
+// Loaded Configuration | 로드된 환경 설정
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub locale: String,
+    pub hotkey: String,
+    pub palette_hotkey: String, // Opens the fuzzy search palette
+    pub editor: String,         // Edit Environment command template, empty for the .ini association
+    pub app_entries: Vec<AppEntry>,
+    pub notify_level: Option<Severity>, // Minimum severity for tray notifications
+    pub tray_icon: Option<(usize, String)>, // (Line, Path) of a custom tray icon
+    pub tooltip: String, // Tray tooltip template: {hotkey}, {count}
+    pub dry_run: bool,   // Log launches instead of running them
+    pub tray_clicks: TrayClicks,
+    pub builtins: BuiltinMenu,
+    pub history: HistorySettings,
+    pub log: LogSettings,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+// Function: Parse Boolean Value | 불리언 값 파싱
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+// Function: Apply [history] Setting | [history] 설정 적용
+fn apply_history_setting(history: &mut HistorySettings, key: &str, value: &str) -> bool {
+    match key.to_lowercase().as_str() {
+        "enabled" => parse_bool(value).map(|b| history.enabled = b).is_some(),
+        "retention_days" => value.parse().map(|n| history.retention_days = n).is_ok(),
+        "max_entries" => value.parse().map(|n| history.max_entries = n).is_ok(),
+        "recent" => value.parse().map(|n| history.recent_count = n).is_ok(),
+        "sort" => match value.to_lowercase().as_str() {
+            "config" => {
+                history.sort_frequent = false;
+                true
+            }
+            "frequent" => {
+                history.sort_frequent = true;
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Function: Apply [log] Setting | [log] 설정 적용
+fn apply_log_setting(log: &mut LogSettings, key: &str, value: &str) -> bool {
+    match key.to_lowercase().as_str() {
+        "enabled" => parse_bool(value).map(|b| log.enabled = b).is_some(),
+        "level" => LogLevel::parse(value).map(|l| log.level = l).is_some(),
+        "dir" => {
+            log.dir = Some(value.trim_matches('"').into());
+            true
+        }
+        "retention_days" => value.parse().map(|n| log.retention_days = n).is_ok(),
+        "max_size_mb" => value.parse().map(|n| log.max_size_mb = n).is_ok(),
+        "format" => match value.to_lowercase().as_str() {
+            "plain" => {
+                log.format = LogFormat::Plain;
+                true
+            }
+            "json" => {
+                log.format = LogFormat::Json;
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Function: Assign Stable IDs | 고정 ID 부여
+// Duplicates are reported and get a "#2", "#3", ... suffix so both items stay usable
+fn assign_ids(app_entries: &mut [AppEntry], diagnostics: &mut Vec<Diagnostic>) {
+    let mut first_line: HashMap<String, usize> = HashMap::new();
+    for entry in app_entries.iter_mut() {
+        let base = entry
+            .options
+            .id
+            .clone()
+            .unwrap_or_else(|| entry.qualified_label());
+
+        if let Some(first) = first_line.get(&base) {
+            let key = if entry.options.id.is_some() {
+                "diag_duplicate_id"
+            } else {
+                "diag_duplicate_label"
+            };
+            diagnostics.push(Diagnostic::new(
+                entry.line,
+                key,
+                &[&base, &first.to_string()],
+            ));
+            let mut n = 2;
+            while first_line.contains_key(&format!("{}#{}", base, n)) {
+                n += 1;
+            }
+            entry.id = format!("{}#{}", base, n);
+        } else {
+            entry.id = base;
+        }
+        first_line.insert(entry.id.clone(), entry.line);
+    }
+}
+
+// Function: Load Config | 환경 설정 로드 함수
+pub fn load_config(ini_path: &Path) -> Config {
+    let contents = fs::read_to_string(ini_path).unwrap_or_default();
+    parse_config(&contents)
+}
+
+// Function: Parse Config Text | 환경 설정 텍스트 파싱
+pub fn parse_config(contents: &str) -> Config {
+    let mut locale = crate::localization::AUTO_LOCALE.to_string();
+    let mut current_section = "".to_string();
+    let mut current_item: Option<String> = None;
+    let mut current_apps_path: Option<Vec<String>> = None;
+    let mut hotkey = "".to_string();
+    let mut palette_hotkey = "".to_string();
+    let mut editor = "".to_string();
+    let mut app_entries: Vec<AppEntry> = Vec::new();
+    let mut notify_level = Some(Severity::Warning);
+    let mut tray_icon: Option<(usize, String)> = None;
+    let mut tooltip = "QikMenu".to_string();
+    let mut dry_run = false;
+    let mut tray_clicks = TrayClicks::default();
+    let mut builtins = BuiltinMenu::default();
+    let mut tray_click_lines = [0; 3]; // Left, right, double; 0 if not set
+    let mut history = HistorySettings::default();
+    let mut log = LogSettings::default();
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    // Label -> (Line, Key, Value) from [item:<Label>] sections
+    let mut item_attrs: HashMap<String, Vec<(usize, String, String)>> = HashMap::new();
+    let mut item_sections: Vec<(usize, String)> = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_no = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            let name = trimmed[1..trimmed.len() - 1].trim();
+            current_section = name.to_lowercase();
+            // Keep the original case of labels for [item:<Label>] and [apps/<Submenu>]
+            current_item = if current_section.starts_with(ITEM_SECTION_PREFIX) {
+                let label = name[ITEM_SECTION_PREFIX.len()..].trim().to_string();
+                item_sections.push((line_no, label.clone()));
+                Some(label)
+            } else {
+                None
+            };
+            current_apps_path = if current_section == "apps" {
+                Some(Vec::new())
+            } else if current_section.starts_with(APPS_SUBMENU_PREFIX) {
+                Some(
+                    name[APPS_SUBMENU_PREFIX.len()..]
+                        .split('/')
+                        .map(|p| p.trim().to_string())
+                        .filter(|p| !p.is_empty())
+                        .collect(),
+                )
+            } else {
+                None
+            };
+            continue;
+        }
+
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let key = key.trim();
+            let value = value.trim();
+
+            if !key.is_empty() && !value.is_empty() {
+                if current_section == "global" {
+                    if key.eq_ignore_ascii_case("locale") {
+                        locale = value.to_lowercase();
+                    } else if key.eq_ignore_ascii_case("notify") {
+                        match Severity::parse_level(value) {
+                            Some(level) => notify_level = level,
+                            None => diagnostics.push(Diagnostic::new(
+                                line_no,
+                                "diag_invalid_notify",
+                                &[value],
+                            )),
+                        }
+                    } else if key.eq_ignore_ascii_case("tray_icon") {
+                        tray_icon = Some((line_no, value.to_string()));
+                    } else if key.eq_ignore_ascii_case("tooltip") {
+                        tooltip = value.to_string();
+                    } else if key.eq_ignore_ascii_case("builtins") {
+                        builtins.items.clear();
+                        let names = value.split(',').map(str::trim);
+                        for name in names.filter(|n| !n.is_empty() && !n.eq_ignore_ascii_case("none")) {
+                            match Builtin::parse(name) {
+                                Some(b) if !builtins.items.contains(&b) => builtins.items.push(b),
+                                Some(_) => {}
+                                None => diagnostics.push(Diagnostic::new(
+                                    line_no,
+                                    "diag_unknown_builtin",
+                                    &[name],
+                                )),
+                            }
+                        }
+                    } else if key.eq_ignore_ascii_case("builtins_submenu") {
+                        match parse_bool(value) {
+                            Some(b) => builtins.submenu = b,
+                            None => diagnostics.push(Diagnostic::new(
+                                line_no,
+                                "diag_invalid_builtins_layout",
+                                &[key, value],
+                            )),
+                        }
+                    } else if key.eq_ignore_ascii_case("builtins_position") {
+                        match value.to_lowercase().as_str() {
+                            "top" => builtins.top = true,
+                            "bottom" => builtins.top = false,
+                            _ => diagnostics.push(Diagnostic::new(
+                                line_no,
+                                "diag_invalid_builtins_layout",
+                                &[key, value],
+                            )),
+                        }
+                    } else if key.eq_ignore_ascii_case("dry_run") {
+                        match parse_bool(value) {
+                            Some(b) => dry_run = b,
+                            None => diagnostics.push(Diagnostic::new(
+                                line_no,
+                                "diag_invalid_dry_run",
+                                &[value],
+                            )),
+                        }
+                    }
+                } else if current_section == "env" {
+                    if key.eq_ignore_ascii_case("hotkey") {
+                        hotkey = value.to_string();
+                    } else if key.eq_ignore_ascii_case("palette_hotkey") {
+                        palette_hotkey = value.to_string();
+                    } else if key.eq_ignore_ascii_case("editor") {
+                        editor = value.to_string();
+                    } else if key.eq_ignore_ascii_case("tray_left_click") {
+                        tray_clicks.left = TrayAction::parse(value);
+                        tray_click_lines[0] = line_no;
+                    } else if key.eq_ignore_ascii_case("tray_right_click") {
+                        tray_clicks.right = TrayAction::parse(value);
+                        tray_click_lines[1] = line_no;
+                    } else if key.eq_ignore_ascii_case("tray_double_click") {
+                        tray_clicks.double = TrayAction::parse(value);
+                        tray_click_lines[2] = line_no;
+                    }
+                } else if current_section == "history" {
+                    if !apply_history_setting(&mut history, key, value) {
+                        diagnostics.push(Diagnostic::new(
+                            line_no,
+                            "diag_invalid_history",
+                            &[key, value],
+                        ));
+                    }
+                } else if current_section == "log" {
+                    if !apply_log_setting(&mut log, key, value) {
+                        diagnostics.push(Diagnostic::new(
+                            line_no,
+                            "diag_invalid_log",
+                            &[key, value],
+                        ));
+                    }
+                } else if let Some(path) = &current_apps_path {
+                    app_entries.push(AppEntry {
+                        id: String::new(), // Assigned below once options are known
+                        path: path.clone(),
+                        label: key.to_string(),
+                        command: value.to_string(),
+                        options: ItemOptions::default(),
+                        line: line_no,
+                    });
+                } else if let Some(label) = &current_item {
+                    item_attrs
+                        .entry(label.clone())
+                        .or_default()
+                        .push((line_no, key.to_string(), value.to_string()));
+                }
+            }
+        }
+    }
+
+    // Attach [item:<Label>] attributes to their entries
+    for (section_line, label) in &item_sections {
+        if !app_entries.iter().any(|e| &e.qualified_label() == label) {
+            diagnostics.push(Diagnostic::new(
+                *section_line,
+                "diag_item_without_app",
+                &[label],
+            ));
+        }
+    }
+    for (label, attrs) in &item_attrs {
+        for entry in app_entries
+            .iter_mut()
+            .filter(|e| &e.qualified_label() == label)
+        {
+            for (line_no, key, value) in attrs {
+                if !entry.options.apply(key, value) {
+                    diagnostics.push(Diagnostic::new(
+                        *line_no,
+                        "diag_invalid_item_option",
+                        &[key, value],
+                    ));
+                }
+            }
+        }
+    }
+    assign_ids(&mut app_entries, &mut diagnostics);
+
+    // Tray clicks may name items, which only have IDs now
+    let click_actions = [&tray_clicks.left, &tray_clicks.right, &tray_clicks.double];
+    for (&line_no, action) in tray_click_lines.iter().zip(click_actions) {
+        if let TrayAction::Run(id) = action {
+            if !app_entries.iter().any(|e| &e.id == id) {
+                diagnostics.push(Diagnostic::new(line_no, "diag_unknown_tray_item", &[id]));
+            }
+        }
+    }
+    diagnostics.sort_by_key(|d| d.line);
+    diagnostics.dedup();
+
+    Config {
+        locale,
+        hotkey,
+        palette_hotkey,
+        editor,
+        app_entries,
+        notify_level,
+        tray_icon,
+        tooltip,
+        dry_run,
+        tray_clicks,
+        builtins,
+        history,
+        log,
+        diagnostics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(config: &Config) -> Vec<&str> {
+        config.app_entries.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn ids_come_from_section_path_and_label() {
+        let config = parse_config("[apps]\nCMD=cmd\n[apps/Dev Tools/Git]\nGUI=git-gui\n");
+        assert_eq!(ids(&config), vec!["CMD", "Dev Tools/Git/GUI"]);
+        assert!(config.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn nested_sections_give_qualified_labels() {
+        let config = parse_config(concat!(
+            "[apps/Dev Tools]\nGit=git-gui\n",
+            "[apps/Dev Tools/Web]\nDocs=https://docs.rs\n",
+        ));
+        let entries = &config.app_entries;
+        assert_eq!(entries[0].path, vec!["Dev Tools"]);
+        assert_eq!(entries[1].path, vec!["Dev Tools", "Web"]);
+        assert_eq!(entries[1].label, "Docs");
+        assert_eq!(entries[1].qualified_label(), "Dev Tools/Web/Docs");
+    }
+
+    #[test]
+    fn explicit_id_overrides_label() {
+        let config = parse_config("[apps]\nCMD=cmd\n[item:CMD]\nid=shell\n");
+        assert_eq!(ids(&config), vec!["shell"]);
+        assert_eq!(config.app_entries[0].options.id.as_deref(), Some("shell"));
+    }
+
+    #[test]
+    fn duplicate_label_gets_suffix_and_diagnostic() {
+        let config = parse_config("[apps]\nCMD=cmd\nNotepad=notepad\nCMD=cmd /k\n");
+        assert_eq!(ids(&config), vec!["CMD", "Notepad", "CMD#2"]);
+        assert_eq!(
+            config.diagnostics,
+            vec![Diagnostic::new(4, "diag_duplicate_label", &["CMD", "2"])]
+        );
+    }
+
+    #[test]
+    fn duplicate_id_gets_diagnostic() {
+        let config = parse_config(concat!(
+            "[apps]\nA=a\nB=b\n",
+            "[item:A]\nid=same\n",
+            "[item:B]\nid=same\n",
+        ));
+        assert_eq!(ids(&config), vec!["same", "same#2"]);
+        assert_eq!(
+            config.diagnostics,
+            vec![Diagnostic::new(3, "diag_duplicate_id", &["same", "2"])]
+        );
+    }
+}
@@ -0,0 +1,472 @@
+use global_hotkey::{GlobalHotKeyEvent, HotKeyState};
+use muda::MenuEvent;
+use tray_icon::{MouseButton, MouseButtonState, TrayIconEvent};
+
+use crate::capture::CaptureReport;
+use crate::ipc::IpcCommand;
+use crate::{
+    MENU_ID_ABOUT, MENU_ID_APP, MENU_ID_EDIT, MENU_ID_EXIT, MENU_ID_OPEN_CONFIG_FOLDER,
+    MENU_ID_OPEN_LOGS, MENU_ID_RECENT, MENU_ID_RELOAD, MENU_ID_RUNNING_FOCUS,
+    MENU_ID_RUNNING_KILL,
+};
+
+// Event Loop User Event | 이벤트 루프 사용자 이벤트
+// Every source wakes the tao event loop through its EventLoopProxy
+#[derive(Debug)]
+pub enum UserEvent {
+    Menu(MenuEvent),
+    Tray(TrayIconEvent),
+    HotKey(GlobalHotKeyEvent),
+    Capture(CaptureReport),
+    Ipc(IpcCommand),
+    PaletteSelected(String), // Item ID
+    TrayClickElapsed(u64),   // Double-click time passed for the click timer with this serial
+}
+
+// Action for the Event Loop | 이벤트 처리 결과
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    EditConfig,
+    Reload,
+    Exit,
+    OpenLogs,
+    OpenConfigFolder,
+    ShowAbout,
+    FocusProcess(u64), // Process registry key
+    TerminateProcess(u64),
+    RunItem(String), // Item ID
+    ShowMenu,
+    ShowAdminMenu, // Edit/Reload/Exit only
+    ShowPalette,
+    StartClickTimer(u64), // Send TrayClickElapsed(serial) after the double-click time
+    RefreshRunning,
+    ReportCapture(CaptureReport),
+    UnknownMenuItem(String),
+}
+
+// Tray Click Action from [env] | 트레이 클릭 동작
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrayAction {
+    Nothing,
+    Menu,        // Full app menu
+    AdminMenu,   // Edit/Reload/Exit only
+    Palette,     // Search palette
+    Run(String), // Item ID
+}
+
+impl TrayAction {
+    // "none", "menu", "admin", "palette"; anything else is an item ID to run
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "none" => TrayAction::Nothing,
+            "menu" => TrayAction::Menu,
+            "admin" => TrayAction::AdminMenu,
+            "palette" => TrayAction::Palette,
+            _ => TrayAction::Run(value.trim().to_string()),
+        }
+    }
+
+    // The [env] value that parses back to this action
+    pub fn value(&self) -> &str {
+        match self {
+            TrayAction::Nothing => "none",
+            TrayAction::Menu => "menu",
+            TrayAction::AdminMenu => "admin",
+            TrayAction::Palette => "palette",
+            TrayAction::Run(id) => id,
+        }
+    }
+
+    fn to_action(&self) -> Option<Action> {
+        match self {
+            TrayAction::Nothing => None,
+            TrayAction::Menu => Some(Action::ShowMenu),
+            TrayAction::AdminMenu => Some(Action::ShowAdminMenu),
+            TrayAction::Palette => Some(Action::ShowPalette),
+            TrayAction::Run(id) => Some(Action::RunItem(id.clone())),
+        }
+    }
+}
+
+// Tray Click Mapping | 트레이 클릭 설정
+// [env] tray_left_click, tray_right_click, tray_double_click
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrayClicks {
+    pub left: TrayAction,
+    pub right: TrayAction,
+    pub double: TrayAction,
+}
+
+impl Default for TrayClicks {
+    fn default() -> Self {
+        Self {
+            left: TrayAction::Menu,
+            right: TrayAction::AdminMenu,
+            double: TrayAction::Nothing,
+        }
+    }
+}
+
+// Left-Click Timing | 왼쪽 클릭 대기 상태
+// With a double-click action set, a left click waits one double-click time before
+// acting, so a double-click does not open the left-click menu first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrayClickState {
+    serial: u64,        // Last click timer started
+    pending: bool,      // A left click waits for timer `serial`
+    after_double: bool, // The button release that ends a double-click is not a click
+}
+
+// Dispatch State | 이벤트 해석 상태
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DispatchState {
+    pub menu_hotkey: Option<u32>,    // HotKey::id() of [env] hotkey
+    pub palette_hotkey: Option<u32>, // HotKey::id() of [env] palette_hotkey
+    pub tray: TrayClicks,
+    pub tray_click: TrayClickState,
+}
+
+// Function: Dispatch Event | 이벤트 해석
+// Pure state transition: decides what to do without touching menus, windows or processes
+pub fn dispatch(state: &mut DispatchState, event: UserEvent) -> Option<Action> {
+    match event {
+        UserEvent::Menu(event) => Some(menu_action(event.id.as_ref())),
+        UserEvent::Ipc(command) => Some(match command {
+            IpcCommand::Show => Action::ShowMenu,
+            IpcCommand::Reload => Action::Reload,
+            IpcCommand::Run(id) => Action::RunItem(id),
+            IpcCommand::Exit => Action::Exit,
+        }),
+        UserEvent::HotKey(event) if event.state == HotKeyState::Pressed => {
+            if Some(event.id) == state.palette_hotkey {
+                Some(Action::ShowPalette)
+            } else if Some(event.id) == state.menu_hotkey {
+                Some(Action::ShowMenu)
+            } else {
+                None
+            }
+        }
+        UserEvent::HotKey(_) => None,
+        UserEvent::Tray(event) => tray_action(&state.tray, &mut state.tray_click, &event),
+        UserEvent::TrayClickElapsed(serial) => {
+            let click = &mut state.tray_click;
+            if click.pending && click.serial == serial {
+                click.pending = false;
+                state.tray.left.to_action()
+            } else {
+                None // Cancelled by a double-click or superseded by a newer click
+            }
+        }
+        UserEvent::Capture(report) => Some(Action::ReportCapture(report)),
+        UserEvent::PaletteSelected(id) => Some(Action::RunItem(id)),
+    }
+}
+
+// Function: Tray Action | 트레이 이벤트 해석
+// Clicks act on button release. Windows reports a double-click as click, double-click,
+// release, so when both left actions are set the first click only starts a timer.
+pub fn tray_action(
+    clicks: &TrayClicks,
+    click: &mut TrayClickState,
+    event: &TrayIconEvent,
+) -> Option<Action> {
+    match event {
+        TrayIconEvent::Click {
+            button,
+            button_state: MouseButtonState::Up,
+            ..
+        } => match button {
+            MouseButton::Left if click.after_double => {
+                click.after_double = false;
+                None
+            }
+            MouseButton::Left
+                if clicks.left != TrayAction::Nothing && clicks.double != TrayAction::Nothing =>
+            {
+                click.serial += 1;
+                click.pending = true;
+                Some(Action::StartClickTimer(click.serial))
+            }
+            MouseButton::Left => clicks.left.to_action(),
+            MouseButton::Right => clicks.right.to_action(),
+            MouseButton::Middle => None,
+        },
+        TrayIconEvent::DoubleClick {
+            button: MouseButton::Left,
+            ..
+        } => {
+            click.pending = false;
+            click.after_double = true;
+            clicks.double.to_action()
+        }
+        // Update uptimes and drop finished processes before a menu opens
+        TrayIconEvent::Enter { .. } => Some(Action::RefreshRunning),
+        _ => None,
+    }
+}
+
+// Function: Menu Action | 메뉴 ID 해석
+pub fn menu_action(id: &str) -> Action {
+    let parse_key = |key: &str| key.parse::<u64>().ok();
+    if id == MENU_ID_EDIT {
+        Action::EditConfig
+    } else if id == MENU_ID_RELOAD {
+        Action::Reload
+    } else if id == MENU_ID_EXIT {
+        Action::Exit
+    } else if id == MENU_ID_OPEN_LOGS {
+        Action::OpenLogs
+    } else if id == MENU_ID_OPEN_CONFIG_FOLDER {
+        Action::OpenConfigFolder
+    } else if id == MENU_ID_ABOUT {
+        Action::ShowAbout
+    } else if let Some(key) = id.strip_prefix(MENU_ID_RUNNING_FOCUS).and_then(parse_key) {
+        Action::FocusProcess(key)
+    } else if let Some(key) = id.strip_prefix(MENU_ID_RUNNING_KILL).and_then(parse_key) {
+        Action::TerminateProcess(key)
+    } else if let Some(item) = id
+        .strip_prefix(MENU_ID_RECENT)
+        .or_else(|| id.strip_prefix(MENU_ID_APP))
+    {
+        Action::RunItem(item.to_string())
+    } else {
+        Action::UnknownMenuItem(id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use muda::MenuId;
+    use tray_icon::{Rect, TrayIconId};
+
+    fn menu(id: &str) -> UserEvent {
+        UserEvent::Menu(MenuEvent {
+            id: MenuId::new(id),
+        })
+    }
+
+    fn hotkey(id: u32, state: HotKeyState) -> UserEvent {
+        UserEvent::HotKey(GlobalHotKeyEvent { id, state })
+    }
+
+    fn state() -> DispatchState {
+        DispatchState {
+            menu_hotkey: Some(1),
+            palette_hotkey: Some(2),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn builtin_menu_ids() {
+        assert_eq!(menu_action(MENU_ID_EDIT), Action::EditConfig);
+        assert_eq!(menu_action(MENU_ID_RELOAD), Action::Reload);
+        assert_eq!(menu_action(MENU_ID_EXIT), Action::Exit);
+        assert_eq!(menu_action(MENU_ID_OPEN_LOGS), Action::OpenLogs);
+        assert_eq!(menu_action(MENU_ID_OPEN_CONFIG_FOLDER), Action::OpenConfigFolder);
+        assert_eq!(menu_action(MENU_ID_ABOUT), Action::ShowAbout);
+    }
+
+    #[test]
+    fn item_menu_id_prefixes() {
+        let app = format!("{}Dev Tools/Git", MENU_ID_APP);
+        assert_eq!(menu_action(&app), Action::RunItem("Dev Tools/Git".to_string()));
+        let recent = format!("{}cmd", MENU_ID_RECENT);
+        assert_eq!(menu_action(&recent), Action::RunItem("cmd".to_string()));
+    }
+
+    #[test]
+    fn running_menu_id_prefixes() {
+        let focus = format!("{}7", MENU_ID_RUNNING_FOCUS);
+        assert_eq!(menu_action(&focus), Action::FocusProcess(7));
+        let kill = format!("{}12", MENU_ID_RUNNING_KILL);
+        assert_eq!(menu_action(&kill), Action::TerminateProcess(12));
+    }
+
+    #[test]
+    fn unknown_menu_ids() {
+        assert_eq!(
+            menu_action("something_else"),
+            Action::UnknownMenuItem("something_else".to_string())
+        );
+        // A running entry without a numeric key is not a process
+        let bad_key = format!("{}abc", MENU_ID_RUNNING_KILL);
+        assert_eq!(menu_action(&bad_key), Action::UnknownMenuItem(bad_key.clone()));
+        assert_eq!(dispatch(&mut state(), menu("x")), Some(Action::UnknownMenuItem("x".to_string())));
+    }
+
+    #[test]
+    fn hotkeys_match_by_id() {
+        let mut state = state();
+        assert_eq!(dispatch(&mut state, hotkey(1, HotKeyState::Pressed)), Some(Action::ShowMenu));
+        assert_eq!(dispatch(&mut state, hotkey(2, HotKeyState::Pressed)), Some(Action::ShowPalette));
+        assert_eq!(dispatch(&mut state, hotkey(3, HotKeyState::Pressed)), None);
+    }
+
+    #[test]
+    fn hotkey_release_is_ignored() {
+        let mut state = state();
+        assert_eq!(dispatch(&mut state, hotkey(1, HotKeyState::Released)), None);
+        assert_eq!(dispatch(&mut state, hotkey(2, HotKeyState::Released)), None);
+    }
+
+    #[test]
+    fn palette_hotkey_wins_when_both_are_the_same() {
+        let mut state = DispatchState {
+            menu_hotkey: Some(5),
+            palette_hotkey: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(dispatch(&mut state, hotkey(5, HotKeyState::Pressed)), Some(Action::ShowPalette));
+    }
+
+    #[test]
+    fn unset_hotkeys_match_nothing() {
+        let mut state = DispatchState::default();
+        assert_eq!(dispatch(&mut state, hotkey(0, HotKeyState::Pressed)), None);
+    }
+
+    #[test]
+    fn ipc_commands() {
+        let mut state = state();
+        let mut ipc = |command| dispatch(&mut state, UserEvent::Ipc(command));
+        assert_eq!(ipc(IpcCommand::Show), Some(Action::ShowMenu));
+        assert_eq!(ipc(IpcCommand::Reload), Some(Action::Reload));
+        assert_eq!(ipc(IpcCommand::Exit), Some(Action::Exit));
+        assert_eq!(
+            ipc(IpcCommand::Run("cmd".to_string())),
+            Some(Action::RunItem("cmd".to_string()))
+        );
+    }
+
+    #[test]
+    fn palette_selection_runs_item() {
+        assert_eq!(
+            dispatch(&mut state(), UserEvent::PaletteSelected("cmd".to_string())),
+            Some(Action::RunItem("cmd".to_string()))
+        );
+    }
+
+    fn click(button: MouseButton, button_state: MouseButtonState) -> UserEvent {
+        UserEvent::Tray(TrayIconEvent::Click {
+            id: TrayIconId::new("tray"),
+            position: Default::default(),
+            rect: Rect::default(),
+            button,
+            button_state,
+        })
+    }
+
+    fn left_up() -> UserEvent {
+        click(MouseButton::Left, MouseButtonState::Up)
+    }
+
+    fn double_click() -> UserEvent {
+        UserEvent::Tray(TrayIconEvent::DoubleClick {
+            id: TrayIconId::new("tray"),
+            position: Default::default(),
+            rect: Rect::default(),
+            button: MouseButton::Left,
+        })
+    }
+
+    fn with_double(action: TrayAction) -> DispatchState {
+        DispatchState {
+            tray: TrayClicks {
+                double: action,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_clicks_act_immediately() {
+        let mut state = DispatchState::default();
+        assert_eq!(dispatch(&mut state, left_up()), Some(Action::ShowMenu));
+        assert_eq!(
+            dispatch(&mut state, click(MouseButton::Right, MouseButtonState::Up)),
+            Some(Action::ShowAdminMenu)
+        );
+        assert_eq!(dispatch(&mut state, click(MouseButton::Middle, MouseButtonState::Up)), None);
+    }
+
+    #[test]
+    fn button_down_is_ignored() {
+        let mut state = DispatchState::default();
+        assert_eq!(dispatch(&mut state, click(MouseButton::Left, MouseButtonState::Down)), None);
+        assert_eq!(dispatch(&mut state, click(MouseButton::Right, MouseButtonState::Down)), None);
+    }
+
+    #[test]
+    fn single_click_waits_for_double_click_time() {
+        let mut state = with_double(TrayAction::Run("cmd".to_string()));
+        assert_eq!(dispatch(&mut state, left_up()), Some(Action::StartClickTimer(1)));
+        assert_eq!(
+            dispatch(&mut state, UserEvent::TrayClickElapsed(1)),
+            Some(Action::ShowMenu)
+        );
+        // The timer fires once
+        assert_eq!(dispatch(&mut state, UserEvent::TrayClickElapsed(1)), None);
+    }
+
+    #[test]
+    fn double_click_cancels_single_click() {
+        let mut state = with_double(TrayAction::Run("cmd".to_string()));
+        // Windows order: release, double-click, release
+        assert_eq!(dispatch(&mut state, left_up()), Some(Action::StartClickTimer(1)));
+        assert_eq!(
+            dispatch(&mut state, double_click()),
+            Some(Action::RunItem("cmd".to_string()))
+        );
+        assert_eq!(dispatch(&mut state, left_up()), None);
+        assert_eq!(dispatch(&mut state, UserEvent::TrayClickElapsed(1)), None);
+
+        // The next click is a normal click again
+        assert_eq!(dispatch(&mut state, left_up()), Some(Action::StartClickTimer(2)));
+        assert_eq!(
+            dispatch(&mut state, UserEvent::TrayClickElapsed(2)),
+            Some(Action::ShowMenu)
+        );
+    }
+
+    #[test]
+    fn stale_click_timer_is_ignored() {
+        let mut state = with_double(TrayAction::Palette);
+        assert_eq!(dispatch(&mut state, left_up()), Some(Action::StartClickTimer(1)));
+        assert_eq!(dispatch(&mut state, left_up()), Some(Action::StartClickTimer(2)));
+        assert_eq!(dispatch(&mut state, UserEvent::TrayClickElapsed(1)), None);
+        assert_eq!(
+            dispatch(&mut state, UserEvent::TrayClickElapsed(2)),
+            Some(Action::ShowMenu)
+        );
+    }
+
+    #[test]
+    fn no_delay_when_left_click_does_nothing() {
+        let mut state = with_double(TrayAction::Palette);
+        state.tray.left = TrayAction::Nothing;
+        assert_eq!(dispatch(&mut state, left_up()), None);
+        assert_eq!(dispatch(&mut state, double_click()), Some(Action::ShowPalette));
+        assert_eq!(dispatch(&mut state, left_up()), None);
+    }
+
+    #[test]
+    fn right_click_is_not_delayed() {
+        let mut state = with_double(TrayAction::Palette);
+        assert_eq!(
+            dispatch(&mut state, click(MouseButton::Right, MouseButtonState::Up)),
+            Some(Action::ShowAdminMenu)
+        );
+    }
+
+    #[test]
+    fn tray_action_parse() {
+        assert_eq!(TrayAction::parse("None"), TrayAction::Nothing);
+        assert_eq!(TrayAction::parse(" menu "), TrayAction::Menu);
+        assert_eq!(TrayAction::parse("admin"), TrayAction::AdminMenu);
+        assert_eq!(TrayAction::parse("palette"), TrayAction::Palette);
+        assert_eq!(TrayAction::parse(" Dev/Git "), TrayAction::Run("Dev/Git".to_string()));
+    }
+}
@@ -0,0 +1,266 @@
+use std::path::Path;
+
+use windows::{
+    core::PWSTR,
+    Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM},
+    Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    },
+    Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindow, GetWindowTextW, GetWindowThreadProcessId, IsIconic,
+        IsWindowVisible, ShowWindow, GW_OWNER, SW_RESTORE,
+    },
+};
+
+use crate::config::ItemOptions;
+
+// Top-level Window Snapshot | 최상위 창 정보
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindowInfo {
+    pub title: String,
+    pub exe_name: String, // File name only, e.g. "chrome.exe"
+    pub pid: u32,
+}
+
+// Window Matching Rules | 창 검색 규칙
+// Every rule that is set must match. Patterns support '*' and '?' and ignore case.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindowMatcher {
+    pub exe: Option<String>,
+    pub title: Option<String>,
+}
+
+impl WindowMatcher {
+    // Build from item options, falling back to the program's image name
+    pub fn for_item(options: &ItemOptions, program: &str) -> Self {
+        let mut matcher = WindowMatcher {
+            exe: options.match_exe.clone(),
+            title: options.match_title.clone(),
+        };
+        if matcher.exe.is_none() && matcher.title.is_none() {
+            matcher.exe = default_exe_name(program);
+        }
+        matcher
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exe.is_none() && self.title.is_none()
+    }
+
+    pub fn matches(&self, window: &WindowInfo) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let exe_ok = self
+            .exe
+            .as_ref()
+            .is_none_or(|p| wildcard_match(p, &window.exe_name));
+        let title_ok = self
+            .title
+            .as_ref()
+            .is_none_or(|p| wildcard_match(p, &window.title));
+        exe_ok && title_ok
+    }
+}
+
+// Function: Default Image Name | 기본 실행 파일 이름
+// "C:\Tools\app" -> "app.exe", "chrome" -> "chrome.exe". URLs have no image name.
+pub fn default_exe_name(program: &str) -> Option<String> {
+    if program.contains("://") {
+        return None;
+    }
+    let name = Path::new(program).file_name()?.to_string_lossy().to_string();
+    if Path::new(&name).extension().is_some() {
+        Some(name)
+    } else {
+        Some(format!("{}.exe", name))
+    }
+}
+
+// Function: Wildcard Match | 와일드카드 비교
+// Case-insensitive; '*' matches any run of characters, '?' exactly one
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+// Function: Process Image Name | 프로세스 실행 파일 이름
+fn process_exe_name(pid: u32) -> String {
+    unsafe {
+        let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return String::new();
+        };
+        let mut buf = [0u16; 1024];
+        let mut len = buf.len() as u32;
+        let res = QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(buf.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(handle);
+        if res.is_err() {
+            return String::new();
+        }
+        let full = String::from_utf16_lossy(&buf[..len as usize]);
+        Path::new(&full)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+unsafe extern "system" fn enum_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let windows = &mut *(lparam.0 as *mut Vec<(HWND, WindowInfo)>);
+
+    // Only visible, unowned windows with a title (what Alt+Tab shows)
+    if !IsWindowVisible(hwnd).as_bool() || GetWindow(hwnd, GW_OWNER).is_ok() {
+        return BOOL(1);
+    }
+    let mut buf = [0u16; 512];
+    let len = GetWindowTextW(hwnd, &mut buf);
+    if len <= 0 {
+        return BOOL(1);
+    }
+    let mut pid = 0u32;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+
+    windows.push((
+        hwnd,
+        WindowInfo {
+            title: String::from_utf16_lossy(&buf[..len as usize]),
+            exe_name: process_exe_name(pid),
+            pid,
+        },
+    ));
+    BOOL(1)
+}
+
+// Function: List Top-level Windows | 최상위 창 목록
+pub fn top_level_windows() -> Vec<(HWND, WindowInfo)> {
+    let mut windows: Vec<(HWND, WindowInfo)> = Vec::new();
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_proc),
+            LPARAM(&mut windows as *mut Vec<(HWND, WindowInfo)> as isize),
+        );
+    }
+    windows
+}
+
+// Function: Find Matching Window | 조건에 맞는 창 찾기
+pub fn find_window(matcher: &WindowMatcher) -> Option<HWND> {
+    top_level_windows()
+        .into_iter()
+        .find(|(_, info)| matcher.matches(info))
+        .map(|(hwnd, _)| hwnd)
+}
+
+// Function: Activate Window | 창 활성화
+// Restores minimized windows, then brings them to the front
+pub fn activate_window(hwnd: HWND) {
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        crate::force_window_foreground(hwnd);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(exe_name: &str, title: &str) -> WindowInfo {
+        WindowInfo {
+            title: title.to_string(),
+            exe_name: exe_name.to_string(),
+            pid: 1,
+        }
+    }
+
+    #[test]
+    fn wildcard_star_backtracks() {
+        assert!(wildcard_match("*Obsidian*", "MyVault - Obsidian v1.5"));
+        assert!(wildcard_match("a*b*c", "a-b-x-b-c"));
+        assert!(wildcard_match("*.exe", "my.app.exe"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("a*b", "a-c"));
+        assert!(!wildcard_match("*.exe", "app.exe.bak"));
+    }
+
+    #[test]
+    fn wildcard_question_matches_one_character() {
+        assert!(wildcard_match("app?.exe", "app2.exe"));
+        assert!(!wildcard_match("app?.exe", "app.exe"));
+        assert!(!wildcard_match("app?.exe", "app12.exe"));
+        assert!(wildcard_match("*?x", "ax"));
+    }
+
+    #[test]
+    fn wildcard_ignores_case() {
+        assert!(wildcard_match("CHROME.EXE", "chrome.exe"));
+        assert!(wildcard_match("*obsidian*", "OBSIDIAN"));
+    }
+
+    #[test]
+    fn default_exe_name_rules() {
+        assert_eq!(default_exe_name("https://example.com"), None);
+        assert_eq!(default_exe_name("obsidian://open"), None);
+        assert_eq!(default_exe_name(r"C:\Tools\app"), Some("app.exe".to_string()));
+        assert_eq!(default_exe_name("chrome"), Some("chrome.exe".to_string()));
+        assert_eq!(default_exe_name("notepad.exe"), Some("notepad.exe".to_string()));
+    }
+
+    #[test]
+    fn matcher_requires_every_rule() {
+        let matcher = WindowMatcher {
+            exe: Some("chrome.exe".to_string()),
+            title: Some("*Mail*".to_string()),
+        };
+        assert!(matcher.matches(&window("Chrome.exe", "Inbox - Mail")));
+        assert!(!matcher.matches(&window("chrome.exe", "News")));
+        assert!(!matcher.matches(&window("msedge.exe", "Inbox - Mail")));
+    }
+
+    #[test]
+    fn empty_matcher_matches_nothing() {
+        assert!(!WindowMatcher::default().matches(&window("app.exe", "App")));
+    }
+
+    #[test]
+    fn for_item_falls_back_to_program_name() {
+        let options = ItemOptions::default();
+        let matcher = WindowMatcher::for_item(&options, r"C:\Tools\app");
+        assert_eq!(matcher.exe.as_deref(), Some("app.exe"));
+        assert_eq!(matcher.title, None);
+
+        let options = ItemOptions {
+            match_title: Some("*Vault*".to_string()),
+            ..Default::default()
+        };
+        let matcher = WindowMatcher::for_item(&options, "obsidian://open");
+        assert_eq!(matcher.exe, None);
+        assert_eq!(matcher.title.as_deref(), Some("*Vault*"));
+    }
+}
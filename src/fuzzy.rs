@@ -0,0 +1,153 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+// Scoring weights | 점수 가중치
+const SCORE_MATCH: i64 = 1;
+const BONUS_CONSECUTIVE: i64 = 5;
+const BONUS_WORD_START: i64 = 8;
+const BONUS_PREFIX: i64 = 10;
+const BONUS_EXACT: i64 = 20;
+const PENALTY_GAP: i64 = 1; // Per skipped character, capped per gap
+const MAX_GAP_PENALTY: i64 = 3;
+
+// Function: Fuzzy Score | 퍼지 점수 계산
+// Every query character must appear in order (case-insensitive). Whitespace in the
+// query is ignored. Returns None if the candidate does not match; higher is better.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let text: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut total = 0;
+    let mut qi = 0;
+    let mut last_match: Option<usize> = None;
+    for (ti, &c) in text.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        if c != query[qi] {
+            continue;
+        }
+        total += SCORE_MATCH;
+        if ti == 0 {
+            total += BONUS_PREFIX;
+        }
+        if ti == 0 || is_separator(text[ti - 1]) {
+            total += BONUS_WORD_START;
+        }
+        match last_match {
+            Some(last) if last + 1 == ti => total += BONUS_CONSECUTIVE,
+            Some(last) => total -= ((ti - last - 1) as i64 * PENALTY_GAP).min(MAX_GAP_PENALTY),
+            None => {}
+        }
+        last_match = Some(ti);
+        qi += 1;
+    }
+    if qi < query.len() {
+        return None;
+    }
+    if text.len() == query.len() {
+        total += BONUS_EXACT;
+    }
+    Some(total)
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '/' | '\\' | '-' | '_' | '.' | ':')
+}
+
+// Function: Rank Candidates | 후보 순위 매기기
+// `candidates` are (key, text) pairs; keys look up launch counts in `counts`.
+// Returns indexes of matching candidates, best first. Equal scores are ordered by
+// launch count, then by their original position.
+pub fn rank(query: &str, candidates: &[(&str, &str)], counts: &HashMap<String, usize>) -> Vec<usize> {
+    let mut matches: Vec<(usize, i64, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (key, text))| {
+            let s = score(query, text)?;
+            Some((i, s, counts.get(*key).copied().unwrap_or(0)))
+        })
+        .collect();
+    matches.sort_by_key(|&(i, s, count)| (Reverse(s), Reverse(count), i));
+    matches.into_iter().map(|(i, _, _)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(pairs: &[(&str, usize)]) -> HashMap<String, usize> {
+        pairs.iter().map(|(k, n)| (k.to_string(), *n)).collect()
+    }
+
+    #[test]
+    fn no_match_returns_none() {
+        assert_eq!(score("xyz", "Google Chrome"), None);
+        // Characters must appear in order
+        assert_eq!(score("cg", "Google Chrome"), None);
+        assert_eq!(score("chromes", "Chrome"), None);
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(score("", "Anything"), Some(0));
+        assert_eq!(score("  ", "Anything"), Some(0));
+    }
+
+    #[test]
+    fn matching_ignores_case_and_query_spaces() {
+        assert!(score("GC", "google chrome").is_some());
+        assert_eq!(score("g c", "Google Chrome"), score("gc", "Google Chrome"));
+    }
+
+    #[test]
+    fn prefix_scores_above_later_match() {
+        let prefix = score("ch", "Chrome").unwrap();
+        let later = score("ch", "Search").unwrap();
+        assert!(prefix > later, "{} vs {}", prefix, later);
+    }
+
+    #[test]
+    fn word_start_scores_above_mid_word() {
+        // 'c' starts a word in "Google Chrome" but not in "Gecko"
+        let word_start = score("gc", "Google Chrome").unwrap();
+        let mid_word = score("gc", "Gecko").unwrap();
+        assert!(word_start > mid_word, "{} vs {}", word_start, mid_word);
+        // Separators other than spaces also start words
+        assert!(score("vc", "vs-code").unwrap() > score("vc", "vscode").unwrap());
+    }
+
+    #[test]
+    fn exact_match_gets_bonus() {
+        let exact = score("cmd", "CMD").unwrap();
+        let longer = score("cmd", "cmder").unwrap();
+        assert_eq!(exact - longer, BONUS_EXACT);
+    }
+
+    #[test]
+    fn rank_orders_by_score() {
+        let candidates = [("a", "Search"), ("b", "Chrome"), ("c", "Notepad")];
+        assert_eq!(rank("ch", &candidates, &HashMap::new()), vec![1, 0]);
+    }
+
+    #[test]
+    fn rank_breaks_ties_by_launch_count() {
+        let candidates = [("a", "Term One"), ("b", "Term Two")];
+        let ranked = rank("term", &candidates, &counts(&[("b", 3), ("a", 1)]));
+        assert_eq!(ranked, vec![1, 0]);
+    }
+
+    #[test]
+    fn rank_breaks_remaining_ties_by_position() {
+        let candidates = [("a", "Term One"), ("b", "Term Two"), ("c", "Term Three")];
+        let ranked = rank("term", &candidates, &counts(&[("c", 2)]));
+        assert_eq!(ranked, vec![2, 0, 1]);
+    }
+}
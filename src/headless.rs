@@ -0,0 +1,471 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use chrono::Local;
+use windows::Win32::System::Console::{
+    AttachConsole, GetStdHandle, ATTACH_PARENT_PROCESS, STD_OUTPUT_HANDLE,
+};
+
+use crate::cli::Subcommand;
+use crate::config::{load_config, AppEntry, Config};
+use crate::history::HistoryStore;
+use crate::json::json_str;
+use crate::launch::{self, join_args, plan_launch};
+use crate::localization::{self, fill, LocalizedStrings, Message};
+use crate::logging::LogFormat;
+use crate::notify::Severity;
+use crate::process_registry::ProcessHandle;
+
+// Exit Codes | 종료 코드
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1; // Config problems or launch failure
+pub const EXIT_USAGE: i32 = 2; // Bad arguments, missing file or unknown item
+
+// Function: Attach Parent Console | 부모 콘솔 연결
+// The GUI subsystem starts without a console; reuse the caller's so println! shows up.
+// Returns false if there is nowhere to print.
+pub fn attach_parent_console() -> bool {
+    unsafe {
+        let redirected = GetStdHandle(STD_OUTPUT_HANDLE).is_ok_and(|h| !h.is_invalid());
+        redirected || AttachConsole(ATTACH_PARENT_PROCESS).is_ok()
+    }
+}
+
+// Function: Run Subcommand | 하위 명령 실행
+// Never creates a window. Returns the process exit code.
+pub fn run(subcommand: &Subcommand, ini_path: &Path, dry_run: bool) -> i32 {
+    match subcommand {
+        Subcommand::Validate(path) => validate(path.as_deref().unwrap_or(ini_path)),
+        Subcommand::List => with_config(ini_path, |config| {
+            list(config, &strings_for(config, ini_path))
+        }),
+        Subcommand::Run(key) => with_config(ini_path, |config| {
+            run_item(config, ini_path, key, dry_run || config.dry_run)
+        }),
+        Subcommand::Dump => with_config(ini_path, |config| {
+            dump_json(config, ini_path, &strings_for(config, ini_path))
+        }),
+    }
+}
+
+fn with_config(ini_path: &Path, f: impl FnOnce(&Config) -> i32) -> i32 {
+    if !ini_path.is_file() {
+        let path = ini_path.display().to_string();
+        let message = Message::new("cli_file_not_found", &[&path]);
+        eprintln!("{}", message.text(&strings_for_path(ini_path)));
+        return EXIT_USAGE;
+    }
+    f(&load_config(ini_path))
+}
+
+// Strings in the config's language, for messages shown to the user
+fn strings_for(config: &Config, ini_path: &Path) -> LocalizedStrings {
+    let locales_dir = localization::locales_dir(ini_path);
+    LocalizedStrings::load(
+        &localization::resolve_locale(&config.locale, &locales_dir),
+        &locales_dir,
+    )
+}
+
+// Function: Strings for Config Path | 설정 파일 기준 언어
+// Used before a config is loaded, e.g. for argument errors; the OS language if the file is missing
+pub fn strings_for_path(ini_path: &Path) -> LocalizedStrings {
+    if ini_path.is_file() {
+        return strings_for(&load_config(ini_path), ini_path);
+    }
+    let locales_dir = localization::locales_dir(ini_path);
+    LocalizedStrings::load(
+        &localization::resolve_locale(localization::AUTO_LOCALE, &locales_dir),
+        &locales_dir,
+    )
+}
+
+// Function: Validate Config | 환경 설정 검사
+// Prints "<file>:<line>: <message>" per problem, like a compiler.
+// Translations missing keys are listed too, but do not fail validation.
+fn validate(ini_path: &Path) -> i32 {
+    with_config(ini_path, |config| {
+        let strings = strings_for(config, ini_path);
+        for d in &config.diagnostics {
+            println!("{}:{}: {}", ini_path.display(), d.line, d.message(&strings));
+        }
+        let locales_dir = localization::locales_dir(ini_path);
+        for (source, missing) in localization::check_translations(&locales_dir) {
+            let missing = missing.join(", ");
+            println!("{}", fill(&strings.cli_missing_translations, &[&source, &missing]));
+        }
+        let path = ini_path.display().to_string();
+        if config.diagnostics.is_empty() {
+            let count = config.app_entries.len().to_string();
+            println!("{}", fill(&strings.cli_validate_ok, &[&path, &count]));
+            EXIT_OK
+        } else {
+            let count = config.diagnostics.len().to_string();
+            println!("{}", fill(&strings.cli_validate_problems, &[&path, &count]));
+            EXIT_FAILURE
+        }
+    })
+}
+
+// Menu Tree for `list` | 목록 출력용 메뉴 트리
+enum Node<'a> {
+    Item(&'a AppEntry),
+    Menu(String, Vec<Node<'a>>),
+}
+
+// Group entries like the tray menu does: submenus appear where they are first used
+fn build_tree(entries: &[AppEntry]) -> Vec<Node<'_>> {
+    let mut root: Vec<Node> = Vec::new();
+    for entry in entries {
+        let mut level = &mut root;
+        for name in &entry.path {
+            let index = level
+                .iter()
+                .position(|n| matches!(n, Node::Menu(m, _) if m == name))
+                .unwrap_or_else(|| {
+                    level.push(Node::Menu(name.clone(), Vec::new()));
+                    level.len() - 1
+                });
+            let Node::Menu(_, children) = &mut level[index] else {
+                unreachable!()
+            };
+            level = children;
+        }
+        level.push(Node::Item(entry));
+    }
+    root
+}
+
+fn print_tree(nodes: &[Node], depth: usize, strings: &LocalizedStrings) {
+    let indent = "  ".repeat(depth);
+    for node in nodes {
+        match node {
+            Node::Menu(name, children) => {
+                println!("{}{}/", indent, name);
+                print_tree(children, depth + 1, strings);
+            }
+            Node::Item(entry) => {
+                println!("{}{}  [id: {}]", indent, entry.label, entry.id);
+                match plan_launch(&entry.command, &entry.options) {
+                    Some(plan) => {
+                        let mut line = format!("{} {}", plan.method.name(), plan.program);
+                        if !plan.args.is_empty() {
+                            line = format!("{} {}", line, join_args(&plan.args));
+                        }
+                        println!("{}    -> {}", indent, line);
+                    }
+                    None => println!("{}    -> {}", indent, strings.cli_list_empty_command),
+                }
+            }
+        }
+    }
+}
+
+// Function: List Items | 항목 목록
+fn list(config: &Config, strings: &LocalizedStrings) -> i32 {
+    print_tree(&build_tree(&config.app_entries), 0, strings);
+    EXIT_OK
+}
+
+// Function: Find Item | 항목 찾기
+// By ID first, then by "<Submenu>/<Label>", then by a unique plain label
+pub fn find_item<'a>(entries: &'a [AppEntry], key: &str) -> Result<&'a AppEntry, Message> {
+    if let Some(e) = entries.iter().find(|e| e.id == key) {
+        return Ok(e);
+    }
+    if let Some(e) = entries.iter().find(|e| e.qualified_label() == key) {
+        return Ok(e);
+    }
+    let matches: Vec<&AppEntry> = entries.iter().filter(|e| e.label == key).collect();
+    match matches.as_slice() {
+        [entry] => Ok(entry),
+        [] => Err(Message::new("cli_no_such_item", &[key])),
+        _ => Err(Message::new("cli_ambiguous_item", &[key])),
+    }
+}
+
+// Function: Run Item | 항목 실행
+// Captured items are waited for and their output and exit code passed through
+fn run_item(config: &Config, ini_path: &Path, key: &str, dry_run: bool) -> i32 {
+    let strings = strings_for(config, ini_path);
+    let entry = match find_item(&config.app_entries, key) {
+        Ok(entry) => entry,
+        Err(e) => {
+            eprintln!("{}", e.text(&strings));
+            return EXIT_USAGE;
+        }
+    };
+    let Some(plan) = plan_launch(&entry.command, &entry.options) else {
+        eprintln!("{}", fill(&strings.cli_empty_command, &[&entry.label]));
+        return EXIT_USAGE;
+    };
+    if dry_run {
+        println!("{}: {}", entry.label, launch::describe(&plan));
+        return EXIT_OK;
+    }
+
+    let code = match launch::execute(&plan) {
+        Ok(Some(ProcessHandle::Child(child))) if plan.capture => match child.wait_with_output() {
+            Ok(output) => {
+                print!("{}", String::from_utf8_lossy(&output.stdout));
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                output.status.code().unwrap_or(EXIT_FAILURE)
+            }
+            Err(e) => {
+                eprintln!("{}: {}", entry.label, e);
+                EXIT_FAILURE
+            }
+        },
+        Ok(_) => EXIT_OK,
+        Err(e) => {
+            eprintln!("{}: {}", entry.label, e);
+            EXIT_FAILURE
+        }
+    };
+
+    let mut history = HistoryStore::load(
+        &ini_path.with_extension("history"),
+        &config.history,
+        Local::now().timestamp(),
+    );
+    crate::record_history(&mut history, &config.history, entry, code == EXIT_OK);
+    code
+}
+
+fn json_opt(s: Option<&str>) -> String {
+    s.map(json_str).unwrap_or_else(|| "null".to_string())
+}
+
+fn json_list(items: &[String]) -> String {
+    let parts: Vec<String> = items.iter().map(|s| json_str(s)).collect();
+    format!("[{}]", parts.join(", "))
+}
+
+fn json_map(map: &BTreeMap<String, String>) -> String {
+    let parts: Vec<String> = map
+        .iter()
+        .map(|(k, v)| format!("{}: {}", json_str(k), json_str(v)))
+        .collect();
+    format!("{{{}}}", parts.join(", "))
+}
+
+fn json_block(lines: &[String]) -> String {
+    if lines.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n  ]", lines.join(",\n"))
+    }
+}
+
+fn severity_name(severity: Option<Severity>) -> Option<&'static str> {
+    severity.map(|s| match s {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    })
+}
+
+fn item_json(entry: &AppEntry) -> String {
+    let plan = plan_launch(&entry.command, &entry.options);
+    let o = &entry.options;
+    format!(
+        concat!(
+            "    {{\"id\": {}, \"path\": {}, \"label\": {}, \"line\": {}, \"command\": {}, ",
+            "\"method\": {}, \"program\": {}, \"args\": {}, ",
+            "\"options\": {{\"elevate\": {}, \"capture\": {}, \"single\": {}, ",
+            "\"match_exe\": {}, \"match_title\": {}, \"icon\": {}, \"labels\": {}}}}}"
+        ),
+        json_str(&entry.id),
+        json_list(&entry.path),
+        json_str(&entry.label),
+        entry.line,
+        json_str(&entry.command),
+        json_opt(plan.as_ref().map(|p| p.method.name())),
+        json_opt(plan.as_ref().map(|p| p.program.as_str())),
+        json_list(plan.as_ref().map(|p| p.args.as_slice()).unwrap_or(&[])),
+        o.elevate,
+        o.capture,
+        o.single,
+        json_opt(o.match_exe.as_deref()),
+        json_opt(o.match_title.as_deref()),
+        json_opt(o.icon.as_deref()),
+        json_map(&o.labels),
+    )
+}
+
+// Function: Dump Config as JSON | 환경 설정 JSON 출력
+fn dump_json(config: &Config, ini_path: &Path, strings: &LocalizedStrings) -> i32 {
+    print!("{}", config_json(config, ini_path, strings));
+    EXIT_OK
+}
+
+// Function: Config as JSON | 환경 설정 JSON
+// One JSON object with every setting, item and diagnostic
+fn config_json(config: &Config, ini_path: &Path, strings: &LocalizedStrings) -> String {
+    let items: Vec<String> = config.app_entries.iter().map(item_json).collect();
+    let diagnostics: Vec<String> = config
+        .diagnostics
+        .iter()
+        .map(|d| {
+            format!(
+                "    {{\"line\": {}, \"key\": {}, \"message\": {}}}",
+                d.line,
+                json_str(d.key),
+                json_str(&d.message(strings))
+            )
+        })
+        .collect();
+    let h = &config.history;
+    let mut out = String::new();
+
+    let _ = writeln!(out, "{{");
+    let _ = writeln!(out, "  \"config\": {},", json_str(&ini_path.to_string_lossy()));
+    let _ = writeln!(out, "  \"locale\": {},", json_str(&config.locale));
+    let _ = writeln!(out, "  \"hotkey\": {},", json_str(&config.hotkey));
+    let _ = writeln!(out, "  \"palette_hotkey\": {},", json_str(&config.palette_hotkey));
+    let _ = writeln!(out, "  \"editor\": {},", json_str(&config.editor));
+    let _ = writeln!(out, "  \"notify\": {},", json_opt(severity_name(config.notify_level)));
+    let _ = writeln!(
+        out,
+        "  \"tray_icon\": {},",
+        json_opt(config.tray_icon.as_ref().map(|(_, path)| path.as_str()))
+    );
+    let _ = writeln!(out, "  \"tooltip\": {},", json_str(&config.tooltip));
+    let _ = writeln!(out, "  \"dry_run\": {},", config.dry_run);
+    let c = &config.tray_clicks;
+    let _ = writeln!(
+        out,
+        "  \"tray_clicks\": {{\"left\": {}, \"right\": {}, \"double\": {}}},",
+        json_str(c.left.value()),
+        json_str(c.right.value()),
+        json_str(c.double.value())
+    );
+    let b = &config.builtins;
+    let names: Vec<String> = b.items.iter().map(|i| i.name().to_string()).collect();
+    let _ = writeln!(
+        out,
+        "  \"builtins\": {{\"items\": {}, \"submenu\": {}, \"position\": {}}},",
+        json_list(&names),
+        b.submenu,
+        json_str(if b.top { "top" } else { "bottom" })
+    );
+    let _ = writeln!(
+        out,
+        "  \"history\": {{\"enabled\": {}, \"retention_days\": {}, \"max_entries\": {}, \"recent\": {}, \"sort\": {}}},",
+        h.enabled,
+        h.retention_days,
+        h.max_entries,
+        h.recent_count,
+        json_str(if h.sort_frequent { "frequent" } else { "config" })
+    );
+    let log = &config.log;
+    let _ = writeln!(
+        out,
+        "  \"log\": {{\"enabled\": {}, \"level\": {}, \"dir\": {}, \"retention_days\": {}, \"max_size_mb\": {}, \"format\": {}}},",
+        log.enabled,
+        json_str(log.level.name()),
+        json_opt(log.dir.as_ref().map(|d| d.to_string_lossy()).as_deref()),
+        log.retention_days,
+        log.max_size_mb,
+        json_str(match log.format {
+            LogFormat::Plain => "plain",
+            LogFormat::Json => "json",
+        })
+    );
+    let _ = writeln!(out, "  \"items\": {},", json_block(&items));
+    let _ = writeln!(out, "  \"diagnostics\": {}", json_block(&diagnostics));
+    let _ = writeln!(out, "}}");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_config;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_ini(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "qikmenu-headless-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("QikMenu.ini");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn english() -> LocalizedStrings {
+        LocalizedStrings::load("en", Path::new("no-such-locales-dir"))
+    }
+
+    #[test]
+    fn validate_exit_codes() {
+        let clean = temp_ini("clean", "[apps]\nCMD=cmd\n");
+        assert_eq!(validate(&clean), EXIT_OK);
+
+        let broken = temp_ini("broken", "[apps]\nCMD=cmd\n[item:Missing]\nelevate=true\n");
+        assert_eq!(validate(&broken), EXIT_FAILURE);
+
+        let missing = clean.with_file_name("Missing.ini");
+        assert_eq!(validate(&missing), EXIT_USAGE);
+        for path in [clean, broken] {
+            let _ = fs::remove_dir_all(path.parent().unwrap());
+        }
+    }
+
+    #[test]
+    fn find_item_by_id_path_and_label() {
+        let config = parse_config(concat!(
+            "[apps]\nCMD=cmd\n",
+            "[apps/Dev]\nGit=git-gui\n",
+            "[item:CMD]\nid=shell\n",
+        ));
+        let entries = &config.app_entries;
+        assert_eq!(find_item(entries, "shell").unwrap().label, "CMD");
+        assert_eq!(find_item(entries, "Dev/Git").unwrap().command, "git-gui");
+        assert_eq!(find_item(entries, "Git").unwrap().command, "git-gui");
+    }
+
+    #[test]
+    fn find_item_reports_missing_and_ambiguous() {
+        let config = parse_config("[apps/A]\nGit=a\n[apps/B]\nGit=b\n");
+        let missing = find_item(&config.app_entries, "Nope").unwrap_err();
+        assert_eq!(missing, Message::new("cli_no_such_item", &["Nope"]));
+        let ambiguous = find_item(&config.app_entries, "Git").unwrap_err();
+        assert_eq!(ambiguous, Message::new("cli_ambiguous_item", &["Git"]));
+        assert_eq!(find_item(&config.app_entries, "B/Git").unwrap().command, "b");
+    }
+
+    #[test]
+    fn config_json_lists_every_section() {
+        let config = parse_config(concat!(
+            "[global]\ndry_run=true\n",
+            "[env]\ntray_left_click=palette\ntray_double_click=CMD\n",
+            "[apps]\nCMD=cmd /k \"echo hi\"\n",
+            "[item:Missing]\nelevate=true\n",
+        ));
+        let json = config_json(&config, Path::new("QikMenu.ini"), &english());
+        assert!(json.starts_with("{\n") && json.ends_with("}\n"));
+        let keys: Vec<&str> = json
+            .lines()
+            .filter_map(|l| l.strip_prefix("  \"")?.split_once('"').map(|(k, _)| k))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "config", "locale", "hotkey", "palette_hotkey", "editor", "notify", "tray_icon",
+                "tooltip", "dry_run", "tray_clicks", "builtins", "history", "log", "items",
+                "diagnostics",
+            ]
+        );
+        assert!(json.contains("  \"dry_run\": true,\n"));
+        let clicks = r#"{"left": "palette", "right": "admin", "double": "CMD"}"#;
+        assert!(json.contains(&format!("  \"tray_clicks\": {},\n", clicks)));
+        assert!(json.contains("\"program\": \"cmd\", \"args\": [\"/k\", \"echo hi\"]"));
+        assert!(json.contains("\"key\": \"diag_item_without_app\""));
+    }
+}
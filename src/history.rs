@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// History Settings from [history] | [history] 사용 기록 설정
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistorySettings {
+    pub enabled: bool,
+    pub retention_days: u32,
+    pub max_entries: usize,
+    pub recent_count: usize, // Items shown in the Recent submenu
+    pub sort_frequent: bool, // Order [apps] by launch count instead of file order
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retention_days: 90,
+            max_entries: 1000,
+            recent_count: 10,
+            sort_frequent: false,
+        }
+    }
+}
+
+// One Launch | 실행 기록 한 건
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaunchRecord {
+    pub timestamp: i64, // Unix seconds
+    pub key: String,    // Item ID, stable across label renames
+    pub label: String,  // Label at launch time
+    pub success: bool,
+}
+
+impl LaunchRecord {
+    // Tab separated: timestamp, success(1/0), key, label
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.timestamp,
+            if self.success { 1 } else { 0 },
+            self.key.replace('\t', " "),
+            self.label.replace('\t', " ")
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut parts = line.splitn(4, '\t');
+        Some(Self {
+            timestamp: parts.next()?.parse().ok()?,
+            success: parts.next()? == "1",
+            key: parts.next()?.to_string(),
+            label: parts.next()?.to_string(),
+        })
+    }
+}
+
+// History Store | 사용 기록 저장소
+// Kept in `QikMenu.history` next to the config, independent of reloads.
+pub struct HistoryStore {
+    path: PathBuf,
+    records: Vec<LaunchRecord>, // Oldest first
+}
+
+impl HistoryStore {
+    // Function: Load History | 사용 기록 로드
+    pub fn load(path: &Path, settings: &HistorySettings, now: i64) -> Self {
+        let records = fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(LaunchRecord::from_line)
+            .collect();
+        let mut store = Self {
+            path: path.to_path_buf(),
+            records,
+        };
+        store.apply_settings(settings, now);
+        store
+    }
+
+    // Prune with new settings, e.g. after a reload, and compact the file if anything was dropped
+    pub fn apply_settings(&mut self, settings: &HistorySettings, now: i64) {
+        if self.prune(settings, now) {
+            self.save();
+        }
+    }
+
+    // Drop records past retention or over the size cap. Returns true if anything was removed.
+    pub fn prune(&mut self, settings: &HistorySettings, now: i64) -> bool {
+        let before = self.records.len();
+        let oldest = now - settings.retention_days as i64 * 24 * 60 * 60;
+        self.records.retain(|r| r.timestamp >= oldest);
+        if self.records.len() > settings.max_entries {
+            let excess = self.records.len() - settings.max_entries;
+            self.records.drain(..excess);
+        }
+        self.records.len() != before
+    }
+
+    // Function: Record Launch | 실행 기록 추가
+    // Appends to the file, or rewrites it when the new record pushes older ones out
+    pub fn record(&mut self, record: LaunchRecord, settings: &HistorySettings) {
+        let line = record.to_line();
+        let now = record.timestamp;
+        self.records.push(record);
+        if self.prune(settings, now) {
+            self.save();
+        } else if let Ok(mut file) = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+        {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
+    fn save(&self) {
+        let contents: String = self
+            .records
+            .iter()
+            .map(|r| r.to_line() + "\n")
+            .collect();
+        let _ = fs::write(&self.path, contents);
+    }
+
+    // Most recent distinct item keys, newest first
+    pub fn recent_keys(&self, count: usize) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for r in self.records.iter().rev() {
+            if keys.len() >= count {
+                break;
+            }
+            if !keys.contains(&r.key) {
+                keys.push(r.key.clone());
+            }
+        }
+        keys
+    }
+
+    // Launch count per item key
+    pub fn counts(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for r in &self.records {
+            *counts.entry(r.key.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn record(timestamp: i64, key: &str) -> LaunchRecord {
+        LaunchRecord {
+            timestamp,
+            key: key.to_string(),
+            label: key.to_string(),
+            success: true,
+        }
+    }
+
+    fn temp_store(name: &str) -> HistoryStore {
+        let path = std::env::temp_dir().join(format!(
+            "qikmenu-history-{}-{}.history",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        HistoryStore {
+            path,
+            records: Vec::new(),
+        }
+    }
+
+    fn file_keys(store: &HistoryStore) -> Vec<String> {
+        fs::read_to_string(&store.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(LaunchRecord::from_line)
+            .map(|r| r.key)
+            .collect()
+    }
+
+    #[test]
+    fn record_applies_max_entries_and_compacts_file() {
+        let settings = HistorySettings {
+            max_entries: 2,
+            ..Default::default()
+        };
+        let mut store = temp_store("max");
+        for (i, key) in ["a", "b", "c"].iter().enumerate() {
+            store.record(record(1000 + i as i64, key), &settings);
+        }
+        assert_eq!(store.recent_keys(10), vec!["c", "b"]);
+        assert_eq!(file_keys(&store), vec!["b", "c"]);
+        let _ = fs::remove_file(&store.path);
+    }
+
+    #[test]
+    fn record_applies_retention() {
+        let settings = HistorySettings {
+            retention_days: 1,
+            ..Default::default()
+        };
+        let mut store = temp_store("retention");
+        store.record(record(0, "old"), &settings);
+        store.record(record(3 * DAY, "new"), &settings);
+        assert_eq!(store.counts().get("old"), None);
+        assert_eq!(file_keys(&store), vec!["new"]);
+        let _ = fs::remove_file(&store.path);
+    }
+
+    #[test]
+    fn apply_settings_prunes_after_reload() {
+        let mut store = temp_store("reload");
+        let keep_all = HistorySettings::default();
+        for key in ["a", "b", "c"] {
+            store.record(record(1000, key), &keep_all);
+        }
+        let smaller = HistorySettings {
+            max_entries: 1,
+            ..Default::default()
+        };
+        store.apply_settings(&smaller, 1000);
+        assert_eq!(file_keys(&store), vec!["c"]);
+        let _ = fs::remove_file(&store.path);
+    }
+
+    #[test]
+    fn history_survives_rename_with_explicit_id() {
+        let settings = HistorySettings::default();
+        let mut store = temp_store("rename");
+        let before = crate::config::parse_config("[apps]\nCMD=cmd\n[item:CMD]\nid=shell\n");
+        let entry = &before.app_entries[0];
+        store.record(
+            LaunchRecord {
+                timestamp: 1000,
+                key: entry.id.clone(),
+                label: entry.label.clone(),
+                success: true,
+            },
+            &settings,
+        );
+
+        let after = crate::config::parse_config(
+            "[apps/Tools]\nCommand Prompt=cmd\n[item:Tools/Command Prompt]\nid=shell\n",
+        );
+        let reloaded = HistoryStore::load(&store.path, &settings, 1000);
+        assert_eq!(reloaded.counts().get(&after.app_entries[0].id), Some(&1));
+        assert_eq!(reloaded.recent_keys(10), vec!["shell"]);
+        let _ = fs::remove_file(&store.path);
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let original = LaunchRecord {
+            timestamp: 42,
+            key: "dev/git".to_string(),
+            label: "Git Bash".to_string(),
+            success: false,
+        };
+        assert_eq!(LaunchRecord::from_line(&original.to_line()), Some(original));
+        assert_eq!(LaunchRecord::from_line("not a record"), None);
+    }
+
+    #[test]
+    fn tabs_in_labels_do_not_split_fields() {
+        let line = record(1, "a\tb").to_line();
+        assert_eq!(LaunchRecord::from_line(&line).map(|r| r.key), Some("a b".to_string()));
+    }
+}
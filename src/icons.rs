@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use image::imageops::FilterType;
+use image::RgbaImage;
+use muda::Icon;
+use windows::{
+    core::HSTRING,
+    Win32::Graphics::Gdi::{
+        CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, SelectObject, BITMAPINFO,
+        BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+    },
+    Win32::UI::Shell::ExtractIconExW,
+    Win32::UI::WindowsAndMessaging::{
+        DestroyIcon, DrawIconEx, GetSystemMetrics, DI_NORMAL, HICON, SM_CXSMICON,
+    },
+};
+
+use crate::launch::parse_cmd;
+
+// Stock icons in shell32.dll used when an item has no `icon=` | 기본 아이콘 (shell32.dll)
+const SHELL32: &str = "shell32.dll";
+const SHELL32_PROGRAM: i32 = 2;
+const SHELL32_FOLDER: i32 = 3;
+const SHELL32_URL: i32 = 13;
+
+// Icon Source from `icon=` | 아이콘 원본
+// "path.png", "path.ico", "path.exe" or "path.dll,3" (resource index)
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct IconSpec {
+    pub path: PathBuf,
+    pub index: Option<i32>,
+}
+
+impl IconSpec {
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim().trim_matches('"');
+        if let Some((path, index)) = value.rsplit_once(',') {
+            if let Ok(index) = index.trim().parse() {
+                return Self {
+                    path: PathBuf::from(path.trim().trim_matches('"')),
+                    index: Some(index),
+                };
+            }
+        }
+        Self {
+            path: PathBuf::from(value),
+            index: None,
+        }
+    }
+
+    // Relative paths start at `dir`, e.g. the config folder
+    pub fn relative_to(mut self, dir: &Path) -> Self {
+        self.path = dir.join(&self.path);
+        self
+    }
+
+    // .exe, .dll and .ico-in-resource files are read through the shell
+    fn is_resource(&self) -> bool {
+        self.index.is_some()
+            || self.path.extension().is_some_and(|e| {
+                e.eq_ignore_ascii_case("exe") || e.eq_ignore_ascii_case("dll")
+            })
+    }
+}
+
+// Action Kind for Default Icons | 기본 아이콘용 실행 종류
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionKind {
+    Url,
+    Folder,
+    Program,
+}
+
+// Function: Classify Command | 명령 종류 판별
+pub fn classify(command: &str) -> ActionKind {
+    let Some(program) = parse_cmd(command).into_iter().next() else {
+        return ActionKind::Program;
+    };
+    if program.contains("://") {
+        ActionKind::Url
+    } else if Path::new(&program).is_dir() {
+        ActionKind::Folder
+    } else {
+        ActionKind::Program
+    }
+}
+
+// Function: Default Icon Spec | 기본 아이콘 지정
+// Programs use their own icon when the executable can be found
+pub fn default_spec(command: &str) -> IconSpec {
+    let stock = |index| IconSpec {
+        path: PathBuf::from(SHELL32),
+        index: Some(index),
+    };
+    match classify(command) {
+        ActionKind::Url => stock(SHELL32_URL),
+        ActionKind::Folder => stock(SHELL32_FOLDER),
+        ActionKind::Program => parse_cmd(command)
+            .first()
+            .and_then(|program| resolve_program(program))
+            .map(|path| IconSpec {
+                path,
+                index: Some(0),
+            })
+            .unwrap_or_else(|| stock(SHELL32_PROGRAM)),
+    }
+}
+
+// Function: Resolve Program Path | 실행 파일 경로 찾기
+// Absolute or relative paths as given, otherwise searched in PATH
+fn resolve_program(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    let with_exe = |p: &Path| {
+        if p.extension().is_some() {
+            p.to_path_buf()
+        } else {
+            p.with_extension("exe")
+        }
+    };
+    if path.components().count() > 1 {
+        return Some(with_exe(path)).filter(|p| p.is_file());
+    }
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| with_exe(&dir.join(program)))
+            .find(|p| p.is_file())
+    })
+}
+
+// Cached Decoded Icon | 캐시된 아이콘
+struct CachedIcon {
+    modified: Option<SystemTime>, // Reloaded when the file changes
+    rgba: Option<Vec<u8>>,        // None if decoding failed
+}
+
+// Icon Cache | 아이콘 캐시
+// Kept for the whole session so reloading the config does not decode icons again
+pub struct IconCache {
+    size: u32,
+    config_dir: PathBuf, // Relative `icon=` paths start here
+    entries: HashMap<IconSpec, CachedIcon>,
+    defaults: HashMap<String, IconSpec>, // `default_spec` per command, resolved once
+}
+
+impl IconCache {
+    pub fn new(config_dir: &Path) -> Self {
+        let size = unsafe { GetSystemMetrics(SM_CXSMICON) };
+        Self {
+            size: if size > 0 { size as u32 } else { 16 },
+            config_dir: config_dir.to_path_buf(),
+            entries: HashMap::new(),
+            defaults: HashMap::new(),
+        }
+    }
+
+    // Function: Configured Icon | 설정된 아이콘
+    // `icon=` value with relative paths resolved against the config folder, like `tray_icon=`.
+    // Names not found there are kept as given so "shell32.dll,4" still finds the system DLL.
+    pub fn configured_spec(&self, value: &str) -> IconSpec {
+        let spec = IconSpec::parse(value);
+        let local = spec.clone().relative_to(&self.config_dir);
+        if local.path.is_file() {
+            local
+        } else {
+            spec
+        }
+    }
+
+    // Function: Default Icon for Command | 명령별 기본 아이콘
+    // Memoized so reloads do not search PATH again for every item
+    pub fn default_spec(&mut self, command: &str) -> IconSpec {
+        self.defaults
+            .entry(command.to_string())
+            .or_insert_with(|| default_spec(command))
+            .clone()
+    }
+
+    // Function: Get Menu Icon | 메뉴 아이콘 가져오기
+    pub fn get(&mut self, spec: &IconSpec) -> Option<Icon> {
+        let modified = std::fs::metadata(&spec.path)
+            .and_then(|m| m.modified())
+            .ok();
+        let stale = self
+            .entries
+            .get(spec)
+            .is_none_or(|cached| cached.modified != modified);
+        if stale {
+            let rgba = load_rgba(spec, self.size);
+            self.entries
+                .insert(spec.clone(), CachedIcon { modified, rgba });
+        }
+        let rgba = self.entries.get(spec)?.rgba.clone()?;
+        Icon::from_rgba(rgba, self.size, self.size).ok()
+    }
+}
+
+// Function: Load Icon Image | 아이콘 이미지 로드
+// Image files keep their own size; resources are rendered at `size`
+pub fn load_image(spec: &IconSpec, size: u32) -> Option<RgbaImage> {
+    if spec.is_resource() {
+        extract_icon(spec, size)
+    } else {
+        Some(image::open(&spec.path).ok()?.into_rgba8())
+    }
+}
+
+// Function: Load Icon Pixels | 아이콘 픽셀 로드
+// Returns `size` x `size` RGBA pixels
+fn load_rgba(spec: &IconSpec, size: u32) -> Option<Vec<u8>> {
+    let image = load_image(spec, size)?;
+    let image = if image.dimensions() == (size, size) {
+        image
+    } else {
+        image::imageops::resize(&image, size, size, FilterType::Lanczos3)
+    };
+    Some(image.into_raw())
+}
+
+// Function: Extract Icon Resource | 아이콘 리소스 추출
+fn extract_icon(spec: &IconSpec, size: u32) -> Option<RgbaImage> {
+    let path = HSTRING::from(spec.path.as_os_str());
+    let mut small = HICON::default();
+    unsafe {
+        let count = ExtractIconExW(&path, spec.index.unwrap_or(0), None, Some(&mut small), 1);
+        if count == 0 || small.is_invalid() {
+            return None;
+        }
+        let image = render_icon(small, size);
+        let _ = DestroyIcon(small);
+        image
+    }
+}
+
+// Draw an HICON into a 32-bit DIB and read back the pixels
+unsafe fn render_icon(icon: HICON, size: u32) -> Option<RgbaImage> {
+    let dc = CreateCompatibleDC(None);
+    let info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: size as i32,
+            biHeight: -(size as i32), // Top-down rows
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
+    let Ok(bitmap) = CreateDIBSection(dc, &info, DIB_RGB_COLORS, &mut bits, None, 0) else {
+        let _ = DeleteDC(dc);
+        return None;
+    };
+    let previous = SelectObject(dc, bitmap);
+    let drawn = DrawIconEx(dc, 0, 0, icon, size as i32, size as i32, 0, None, DI_NORMAL);
+
+    let len = (size * size * 4) as usize;
+    let mut pixels = std::slice::from_raw_parts(bits as *const u8, len).to_vec();
+    SelectObject(dc, previous);
+    let _ = DeleteObject(bitmap);
+    let _ = DeleteDC(dc);
+    drawn.ok()?;
+
+    // BGRA -> RGBA; for icons without an alpha channel, treat drawn pixels as opaque
+    let has_alpha = pixels.chunks_exact(4).any(|p| p[3] != 0);
+    for p in pixels.chunks_exact_mut(4) {
+        p.swap(0, 2);
+        if !has_alpha && (p[0] | p[1] | p[2]) != 0 {
+            p[3] = 255;
+        }
+    }
+    RgbaImage::from_raw(size, size, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn spec(path: &str, index: Option<i32>) -> IconSpec {
+        IconSpec {
+            path: PathBuf::from(path),
+            index,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "qikmenu-icons-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_path_and_index() {
+        assert_eq!(IconSpec::parse("shell32.dll,4"), spec("shell32.dll", Some(4)));
+        assert_eq!(IconSpec::parse("\"C:\\Apps\\app.exe\", 1"), spec(r"C:\Apps\app.exe", Some(1)));
+        assert_eq!(IconSpec::parse("icons/app.png"), spec("icons/app.png", None));
+    }
+
+    #[test]
+    fn parse_negative_index_is_resource_id() {
+        assert_eq!(IconSpec::parse("imageres.dll,-5302"), spec("imageres.dll", Some(-5302)));
+    }
+
+    #[test]
+    fn parse_keeps_commas_inside_path() {
+        assert_eq!(IconSpec::parse(r"C:\My, Icons\app.png"), spec(r"C:\My, Icons\app.png", None));
+        assert_eq!(IconSpec::parse(r"C:\a,b\tools.dll,3"), spec(r"C:\a,b\tools.dll", Some(3)));
+    }
+
+    #[test]
+    fn classify_urls_folders_and_programs() {
+        let dir = temp_dir("classify");
+        assert_eq!(classify("https://example.com"), ActionKind::Url);
+        assert_eq!(classify("obsidian://open/?vault=Work"), ActionKind::Url);
+        assert_eq!(classify(&format!("\"{}\"", dir.display())), ActionKind::Folder);
+        assert_eq!(classify("notepad.exe notes.txt"), ActionKind::Program);
+        assert_eq!(classify(""), ActionKind::Program);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn default_spec_picks_stock_or_program_icon() {
+        let dir = temp_dir("default");
+        let program = dir.join("tool.exe");
+        fs::write(&program, b"").unwrap();
+
+        assert_eq!(default_spec("https://example.com"), spec(SHELL32, Some(SHELL32_URL)));
+        let folder = format!("\"{}\"", dir.display());
+        assert_eq!(default_spec(&folder), spec(SHELL32, Some(SHELL32_FOLDER)));
+        let command = format!("\"{}\" --flag", program.display());
+        assert_eq!(default_spec(&command), IconSpec { path: program, index: Some(0) });
+        assert_eq!(
+            default_spec("no-such-program-qikmenu"),
+            spec(SHELL32, Some(SHELL32_PROGRAM))
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cache_resolves_default_spec_once_per_command() {
+        let dir = temp_dir("memo");
+        let mut cache = IconCache {
+            size: 16,
+            config_dir: dir.clone(),
+            entries: HashMap::new(),
+            defaults: HashMap::new(),
+        };
+        let folder = format!("\"{}\"", dir.display());
+        assert_eq!(cache.default_spec(&folder), spec(SHELL32, Some(SHELL32_FOLDER)));
+        fs::remove_dir_all(&dir).unwrap();
+        // The folder is gone, but the first answer is kept
+        assert_eq!(cache.default_spec(&folder), spec(SHELL32, Some(SHELL32_FOLDER)));
+        assert_eq!(default_spec(&folder), spec(SHELL32, Some(SHELL32_PROGRAM)));
+    }
+}
@@ -0,0 +1,251 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+// Connection attempts while the server is between clients | 연결 재시도
+const SEND_RETRIES: u32 = 10;
+const RETRY_DELAY: Duration = Duration::from_millis(50);
+// Failed accepts in a row before the server gives up; the delay doubles each time
+const MAX_ACCEPT_FAILURES: u32 = 10;
+
+// Command Forwarded to the Running Instance | 실행 중인 인스턴스로 보내는 명령
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IpcCommand {
+    Show,
+    Reload,
+    Run(String), // Item ID
+    Exit,
+}
+
+impl IpcCommand {
+    // One command per line: "show", "reload", "run <id>", "exit"
+    pub fn to_line(&self) -> String {
+        match self {
+            IpcCommand::Show => "show".to_string(),
+            IpcCommand::Reload => "reload".to_string(),
+            IpcCommand::Run(id) => format!("run {}", id),
+            IpcCommand::Exit => "exit".to_string(),
+        }
+    }
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let (verb, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match verb {
+            "show" => Some(IpcCommand::Show),
+            "reload" => Some(IpcCommand::Reload),
+            "run" if !rest.trim().is_empty() => Some(IpcCommand::Run(rest.trim().to_string())),
+            "exit" => Some(IpcCommand::Exit),
+            _ => None,
+        }
+    }
+}
+
+// Function: Endpoint Name | 통신 주소
+// Named pipe per session and instance key. Pipe names are machine-wide, while the
+// single-instance lock is per session, so another user's instance needs its own pipe.
+pub fn endpoint_name(instance_key: &str) -> String {
+    format!(r"\\.\pipe\QikMenu_{}_{}", session_id(), instance_key)
+}
+
+fn session_id() -> u32 {
+    use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+    use windows::Win32::System::Threading::GetCurrentProcessId;
+
+    let mut session = 0u32;
+    let _ = unsafe { ProcessIdToSessionId(GetCurrentProcessId(), &mut session) };
+    session
+}
+
+// Function: Send Command | 명령 전송
+pub fn send(endpoint: &str, command: &IpcCommand) -> io::Result<()> {
+    let mut last_error = None;
+    for _ in 0..SEND_RETRIES {
+        match connect(endpoint) {
+            Ok(mut stream) => return writeln!(stream, "{}", command.to_line()),
+            Err(e) => last_error = Some(e),
+        }
+        thread::sleep(RETRY_DELAY);
+    }
+    Err(last_error.unwrap_or_else(|| io::Error::other("IPC connect failed")))
+}
+
+// Function: Serve Commands | 명령 수신
+// Accepts clients in a background thread and forwards parsed commands to `tx`.
+// Fails if the pipe already exists, e.g. when another process owns the name.
+pub fn serve(endpoint: &str, tx: Sender<IpcCommand>) -> io::Result<()> {
+    let mut listener = Listener::bind(endpoint)?;
+    thread::spawn(move || {
+        let mut failures = 0;
+        loop {
+            let reader = match listener.accept() {
+                Ok(reader) => {
+                    failures = 0;
+                    reader
+                }
+                Err(e) => {
+                    failures += 1;
+                    crate::log_msg("ERROR", &format!("IPC Accept Failed: {}", e));
+                    if failures >= MAX_ACCEPT_FAILURES {
+                        crate::log_msg("ERROR", "IPC Server Stopped After Repeated Failures");
+                        return;
+                    }
+                    thread::sleep(RETRY_DELAY * 2u32.pow(failures - 1));
+                    continue;
+                }
+            };
+            if !forward(reader, &tx) {
+                return; // Event loop is gone
+            }
+        }
+    });
+    Ok(())
+}
+
+// Sends every command line from one client; false once the receiver is gone
+fn forward(reader: std::fs::File, tx: &Sender<IpcCommand>) -> bool {
+
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        match IpcCommand::parse(&line) {
+            Some(command) => {
+                if tx.send(command).is_err() {
+                    return false;
+                }
+            }
+            None => crate::log_msg("WARN", &format!("Unknown IPC Command: {}", line)),
+        }
+    }
+    true
+}
+
+fn connect(endpoint: &str) -> io::Result<std::fs::File> {
+    // A named pipe client is opened like a file
+    std::fs::OpenOptions::new().write(true).open(endpoint)
+}
+
+struct Listener {
+    name: windows::core::HSTRING,
+    first: Option<OwnedHandle>, // Created by `bind`, handed out by the first `accept`
+}
+
+impl Listener {
+    // Creates the first pipe instance so a name already in use is reported here
+    fn bind(endpoint: &str) -> io::Result<Self> {
+        let name = windows::core::HSTRING::from(endpoint);
+        let first = create_pipe(&name, true)?;
+        Ok(Self {
+            name,
+            first: Some(first),
+        })
+    }
+
+    // Waits for a client on the next pipe instance, one instance per client
+    fn accept(&mut self) -> io::Result<std::fs::File> {
+        use windows::Win32::Foundation::{ERROR_PIPE_CONNECTED, HANDLE};
+        use windows::Win32::System::Pipes::ConnectNamedPipe;
+
+        let pipe = match self.first.take() {
+            Some(pipe) => pipe,
+            None => create_pipe(&self.name, false)?,
+        };
+        if let Err(e) = unsafe { ConnectNamedPipe(HANDLE(pipe.as_raw_handle()), None) } {
+            if e.code() != ERROR_PIPE_CONNECTED.to_hresult() {
+                return Err(io::Error::other(e)); // Dropping `pipe` closes it
+            }
+        }
+        // The File owns the handle and closes it when the client is done
+        Ok(std::fs::File::from(pipe))
+    }
+}
+
+fn create_pipe(name: &windows::core::HSTRING, first: bool) -> io::Result<OwnedHandle> {
+    use windows::Win32::Storage::FileSystem::{
+        FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_INBOUND,
+    };
+    use windows::Win32::System::Pipes::{
+        CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE,
+        PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+    };
+
+    let mut open_mode = PIPE_ACCESS_INBOUND;
+    if first {
+        // Fail instead of joining a pipe that another process created
+        open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+    }
+    unsafe {
+        let pipe = CreateNamedPipeW(
+            name,
+            open_mode,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            0,
+            4096,
+            0,
+            None,
+        );
+        if pipe.is_invalid() {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(OwnedHandle::from_raw_handle(pipe.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn parse_round_trips_every_command() {
+        let commands = [
+            IpcCommand::Show,
+            IpcCommand::Reload,
+            IpcCommand::Run("Dev Tools/Git".to_string()),
+            IpcCommand::Exit,
+        ];
+        for command in commands {
+            assert_eq!(IpcCommand::parse(&command.to_line()), Some(command));
+        }
+    }
+
+    #[test]
+    fn parse_trims_and_rejects_unknown() {
+        assert_eq!(IpcCommand::parse("  reload \r"), Some(IpcCommand::Reload));
+        assert_eq!(IpcCommand::parse("run   cmd "), Some(IpcCommand::Run("cmd".to_string())));
+        assert_eq!(IpcCommand::parse("run"), None);
+        assert_eq!(IpcCommand::parse("run   "), None);
+        assert_eq!(IpcCommand::parse("restart"), None);
+        assert_eq!(IpcCommand::parse(""), None);
+    }
+
+    #[test]
+    fn serve_receives_sent_commands() {
+        let endpoint = endpoint_name(&format!("test_{}", std::process::id()));
+        let (tx, rx) = mpsc::channel();
+        serve(&endpoint, tx).unwrap();
+
+        let run = IpcCommand::Run("Chrome".to_string());
+        send(&endpoint, &run).unwrap();
+        send(&endpoint, &IpcCommand::Reload).unwrap();
+
+        let timeout = Duration::from_secs(5);
+        assert_eq!(rx.recv_timeout(timeout), Ok(run));
+        assert_eq!(rx.recv_timeout(timeout), Ok(IpcCommand::Reload));
+    }
+
+    #[test]
+    fn serve_fails_when_endpoint_is_taken() {
+        let endpoint = endpoint_name(&format!("taken_{}", std::process::id()));
+        let (tx, _rx) = mpsc::channel();
+        serve(&endpoint, tx.clone()).unwrap();
+        assert!(serve(&endpoint, tx).is_err());
+    }
+
+    #[test]
+    fn endpoint_name_includes_session() {
+        let name = endpoint_name("abc");
+        assert_eq!(name, format!(r"\\.\pipe\QikMenu_{}_abc", session_id()));
+    }
+}
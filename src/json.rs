@@ -0,0 +1,35 @@
+// Function: JSON String | JSON 문자열
+pub fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_str_escapes_quotes_and_backslashes() {
+        assert_eq!(json_str(r#"C:\Tools\"app".exe"#), r#""C:\\Tools\\\"app\".exe""#);
+    }
+
+    #[test]
+    fn json_str_escapes_control_characters() {
+        assert_eq!(json_str("a\nb\r\tc"), r#""a\nb\r\tc""#);
+        assert_eq!(json_str("\u{1}\u{1f}"), r#""\u0001\u001f""#);
+        assert_eq!(json_str("한국어 ✓"), "\"한국어 ✓\"");
+    }
+}
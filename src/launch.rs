@@ -0,0 +1,367 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config::ItemOptions;
+use crate::process_registry::{ProcessHandle, ShellProcess};
+use crate::template;
+
+// Launch Method | 실행 방식
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchMethod {
+    Spawn,    // Program + Args via Command::new
+    Open,     // Single part via ShellExecute (URLs, Files, Folders)
+    Elevated, // ShellExecute "runas" (Run as administrator)
+}
+
+impl LaunchMethod {
+    // Lowercase name for logs and `list`/`dump` output
+    pub fn name(&self) -> &'static str {
+        match self {
+            LaunchMethod::Spawn => "spawn",
+            LaunchMethod::Open => "open",
+            LaunchMethod::Elevated => "elevated",
+        }
+    }
+}
+
+// Launch Plan | 실행 계획
+// Decided without touching the OS so it can be checked independently.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaunchPlan {
+    pub method: LaunchMethod,
+    pub program: String,
+    pub args: Vec<String>,
+    pub capture: bool, // Pipe stdout/stderr and wait for the exit code
+}
+
+// Launch Error | 실행 오류
+#[derive(Debug)]
+pub enum LaunchError {
+    Cancelled, // User declined the UAC prompt
+    Io(io::Error),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchError::Cancelled => write!(f, "Cancelled by user (UAC)"),
+            LaunchError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+// Function: Configure Command | 명령어 파싱 함수
+// Splits string by spaces but respects quotes
+pub fn parse_cmd(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == ' ' && !in_quotes {
+            if !current.is_empty() {
+                args.push(current.clone());
+                current.clear();
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+// Function: Plan Launch | 실행 계획 수립
+pub fn plan_launch(command: &str, options: &ItemOptions) -> Option<LaunchPlan> {
+    let mut parts = parse_cmd(command);
+    if parts.is_empty() {
+        return None;
+    }
+    let program = parts.remove(0);
+
+    // Elevated processes can't be piped, so capture only applies to Spawn
+    let method = if options.elevate {
+        LaunchMethod::Elevated
+    } else if parts.is_empty() && !options.capture {
+        LaunchMethod::Open
+    } else {
+        LaunchMethod::Spawn
+    };
+
+    Some(LaunchPlan {
+        method,
+        program,
+        args: parts,
+        capture: options.capture && method == LaunchMethod::Spawn,
+    })
+}
+
+// Function: Plan Editor Launch | 편집기 실행 계획
+// `editor` is a command template such as `code -g {file}:{line}`. It is split with
+// parse_cmd before {file} and {line} are filled in, so paths with spaces stay one argument.
+// Templates without {file} get the file as the last argument.
+pub fn plan_editor(editor: &str, file: &Path, line: usize) -> Option<LaunchPlan> {
+    let file = file.display().to_string();
+    let line = line.to_string();
+    let vars = [("file", file.as_str()), ("line", line.as_str())];
+    let mut parts: Vec<String> = parse_cmd(editor)
+        .iter()
+        .map(|part| template::render(part, &vars))
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    if !editor.contains("{file}") {
+        parts.push(file);
+    }
+    let program = parts.remove(0);
+    Some(LaunchPlan {
+        method: LaunchMethod::Spawn,
+        program,
+        args: parts,
+        capture: false,
+    })
+}
+
+// Function: Summarize Launch Plan | 실행 계획 요약
+// Program, arguments and elevation on one line, short enough for a notification
+pub fn summary(plan: &LaunchPlan) -> String {
+    let args: Vec<String> = plan.args.iter().map(|a| format!("{:?}", a)).collect();
+    format!(
+        "method={} program={:?} args=[{}] elevated={} capture={}",
+        plan.method.name(),
+        plan.program,
+        args.join(", "),
+        plan.method == LaunchMethod::Elevated,
+        plan.capture
+    )
+}
+
+// Function: Describe Launch Plan | 실행 계획 설명
+// Everything a launch would use, for dry runs. Items inherit QikMenu's cwd and environment.
+// Only variable names are listed: the log may be shared and values can hold secrets.
+pub fn describe(plan: &LaunchPlan) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let names: Vec<String> = std::env::vars_os()
+        .map(|(k, _)| k.to_string_lossy().to_string())
+        .collect();
+    describe_in(plan, &cwd, &names)
+}
+
+// `describe` for a given cwd and environment variable names, listed sorted
+pub fn describe_in(plan: &LaunchPlan, cwd: &Path, env_names: &[String]) -> String {
+    let mut names = env_names.to_vec();
+    names.sort_by_key(|name| name.to_lowercase());
+    format!(
+        "{} cwd={:?} env=inherited[{}]",
+        summary(plan),
+        cwd.display().to_string(),
+        names.join(", ")
+    )
+}
+
+// Function: Join Arguments | 인자 합치기
+// Re-quotes arguments containing spaces for ShellExecute's single parameter string
+pub fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|a| {
+            if a.is_empty() || a.contains(' ') {
+                format!("\"{}\"", a)
+            } else {
+                a.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Function: Execute Launch Plan | 실행 계획 수행
+// Returns the started process so the caller can keep or watch it. None when the shell
+// handed the request to an already running program, e.g. a URL opened in a browser tab.
+pub fn execute(plan: &LaunchPlan) -> Result<Option<ProcessHandle>, LaunchError> {
+    match plan.method {
+        LaunchMethod::Spawn => {
+            let mut command = Command::new(&plan.program);
+            command.args(&plan.args);
+            if plan.capture {
+                use std::os::windows::process::CommandExt;
+                const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+                command
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .creation_flags(CREATE_NO_WINDOW);
+            }
+            command
+                .spawn()
+                .map(|child| Some(ProcessHandle::Child(child)))
+                .map_err(LaunchError::Io)
+        }
+        LaunchMethod::Open => shell_execute(None, &plan.program, ""),
+        LaunchMethod::Elevated => {
+            shell_execute(Some("runas"), &plan.program, &join_args(&plan.args))
+        }
+    }
+}
+
+// Function: ShellExecuteEx | 셸 실행
+// `verb` None uses the default action (open); "runas" runs as administrator.
+// SEE_MASK_NOCLOSEPROCESS keeps the process handle so the launch can be tracked.
+fn shell_execute(
+    verb: Option<&str>,
+    file: &str,
+    params: &str,
+) -> Result<Option<ProcessHandle>, LaunchError> {
+    use windows::core::{HRESULT, HSTRING, PCWSTR};
+    use windows::Win32::Foundation::ERROR_CANCELLED;
+    use windows::Win32::UI::Shell::{
+        ShellExecuteExW, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let verb_h = verb.map(HSTRING::from);
+    let file_h = HSTRING::from(file);
+    let params_h = HSTRING::from(params);
+
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS,
+        lpVerb: verb_h
+            .as_ref()
+            .map_or(PCWSTR::null(), |v| PCWSTR(v.as_ptr())),
+        lpFile: PCWSTR(file_h.as_ptr()),
+        lpParameters: if params.is_empty() {
+            PCWSTR::null()
+        } else {
+            PCWSTR(params_h.as_ptr())
+        },
+        nShow: SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+
+    match unsafe { ShellExecuteExW(&mut info) } {
+        Ok(()) if info.hProcess.is_invalid() => Ok(None),
+        Ok(()) => Ok(Some(ProcessHandle::Shell(ShellProcess::from_handle(
+            info.hProcess,
+        )))),
+        Err(e) if e.code() == HRESULT::from_win32(ERROR_CANCELLED.0) => {
+            Err(LaunchError::Cancelled)
+        }
+        Err(e) => Err(LaunchError::Io(io::Error::other(e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(elevate: bool, capture: bool) -> ItemOptions {
+        ItemOptions {
+            elevate,
+            capture,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn elevate_uses_elevated() {
+        let plan = plan_launch("cmd /k dir", &options(true, false)).unwrap();
+        assert_eq!(plan.method, LaunchMethod::Elevated);
+        assert_eq!(plan.program, "cmd");
+        assert_eq!(plan.args, vec!["/k", "dir"]);
+    }
+
+    #[test]
+    fn single_part_uses_open() {
+        let plan = plan_launch("https://example.com", &options(false, false)).unwrap();
+        assert_eq!(plan.method, LaunchMethod::Open);
+        assert!(plan.args.is_empty());
+        assert!(!plan.capture);
+    }
+
+    #[test]
+    fn single_part_with_capture_uses_spawn() {
+        let plan = plan_launch("ipconfig", &options(false, true)).unwrap();
+        assert_eq!(plan.method, LaunchMethod::Spawn);
+        assert!(plan.capture);
+    }
+
+    #[test]
+    fn elevate_disables_capture() {
+        let plan = plan_launch("ipconfig /all", &options(true, true)).unwrap();
+        assert_eq!(plan.method, LaunchMethod::Elevated);
+        assert!(!plan.capture);
+    }
+
+    #[test]
+    fn empty_command_has_no_plan() {
+        assert_eq!(plan_launch("", &options(false, false)), None);
+        assert_eq!(plan_launch("   ", &options(true, false)), None);
+    }
+
+    #[test]
+    fn quoted_program_keeps_spaces() {
+        let plan = plan_launch(
+            r#""C:\Program Files\App\app.exe" --flag"#,
+            &options(false, false),
+        )
+        .unwrap();
+        assert_eq!(plan.program, r"C:\Program Files\App\app.exe");
+        assert_eq!(plan.args, vec!["--flag"]);
+    }
+
+    #[test]
+    fn describe_lists_cwd_and_sorted_env() {
+        let plan = plan_launch("cmd /k \"echo hi\"", &options(true, false)).unwrap();
+        let env = vec!["Path".to_string(), "APPDATA".to_string(), "api_token".to_string()];
+        assert_eq!(
+            describe_in(&plan, Path::new("work"), &env),
+            r#"method=elevated program="cmd" args=["/k", "echo hi"] elevated=true capture=false cwd="work" env=inherited[api_token, APPDATA, Path]"#
+        );
+    }
+
+    #[test]
+    fn plan_editor_fills_file_and_line() {
+        let plan = plan_editor("code -g {file}:{line}", Path::new("QikMenu.ini"), 12).unwrap();
+        assert_eq!(plan.method, LaunchMethod::Spawn);
+        assert_eq!(plan.program, "code");
+        assert_eq!(plan.args, vec!["-g", "QikMenu.ini:12"]);
+    }
+
+    #[test]
+    fn plan_editor_keeps_path_with_spaces_as_one_argument() {
+        let file = Path::new("My Configs/QikMenu.ini");
+        let plan = plan_editor("\"C:\\Program Files\\Editor\\ed.exe\" {file}", file, 1).unwrap();
+        assert_eq!(plan.program, r"C:\Program Files\Editor\ed.exe");
+        assert_eq!(plan.args, vec!["My Configs/QikMenu.ini"]);
+    }
+
+    #[test]
+    fn plan_editor_appends_file_without_placeholder() {
+        let plan = plan_editor("notepad++ -n{line}", Path::new("a b.ini"), 3).unwrap();
+        assert_eq!(plan.program, "notepad++");
+        assert_eq!(plan.args, vec!["-n3", "a b.ini"]);
+    }
+
+    #[test]
+    fn plan_editor_empty_template_has_no_plan() {
+        assert_eq!(plan_editor("", Path::new("QikMenu.ini"), 1), None);
+        assert_eq!(plan_editor("   ", Path::new("QikMenu.ini"), 1), None);
+    }
+
+    #[test]
+    fn join_args_quotes_spaces_and_empty() {
+        let args: Vec<String> = ["/k", "C:\\My Files", "", "plain"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(join_args(&args), r#"/k "C:\My Files" "" plain"#);
+        assert_eq!(join_args(&[]), "");
+    }
+}
@@ -1,35 +1,385 @@
-pub struct LocalizedStrings {
-    pub edit_environment: String,
-    pub reload: String,
-    pub exit: String,
-    pub warning_title: String,
-    pub warning_msg: String,
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Built-in translations, also the templates for new languages | 내장 번역
+const EMBEDDED: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.ini")),
+    ("ja", include_str!("locales/ja.ini")),
+    ("ko", include_str!("locales/ko.ini")),
+];
+// Last entry of every fallback chain | 최종 대체 언어
+const FALLBACK_LOCALE: &str = "en";
+// `locale=` value that follows the OS language | OS 언어 사용 설정값
+pub const AUTO_LOCALE: &str = "auto";
+
+// Declares the string fields and the matching translation keys together
+macro_rules! localized_strings {
+    ($($key:ident),* $(,)?) => {
+        pub struct LocalizedStrings {
+            $(pub $key: String,)*
+        }
+
+        // Keys every translation should define | 번역 키 목록
+        pub const KEYS: &[&str] = &[$(stringify!($key)),*];
+
+        impl LocalizedStrings {
+            fn from_catalog(catalog: &HashMap<String, String>) -> Self {
+                Self {
+                    $($key: catalog.get(stringify!($key)).cloned().unwrap_or_default(),)*
+                }
+            }
+
+            // Looks up a message by key; unknown keys come back as-is
+            pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+                match key {
+                    $(stringify!($key) => &self.$key,)*
+                    _ => key,
+                }
+            }
+        }
+    };
+}
+
+localized_strings! {
+    edit_environment,
+    reload,
+    exit,
+    open_logs,
+    open_config_folder,
+    about,
+    warning_title,
+    warning_msg,
+    recent,
+    running,
+    focus,
+    terminate,
+    // Notifications: {0}, {1}, ... are filled by `fill`
+    exec_failed,
+    elevation_cancelled,
+    process_failed,
+    hotkey_failed,
+    config_problems,
+    dry_run,
+    // About box: {0} version, {1} config path, {2} hotkey, {3} item count, {4} problems
+    about_text,
+    about_no_hotkey,
+    about_no_problems,
+    about_problems,
+    // Config diagnostics, see `config::Diagnostic`
+    diag_invalid_notify,
+    diag_invalid_dry_run,
+    diag_invalid_history,
+    diag_invalid_log,
+    diag_item_without_app,
+    diag_invalid_item_option,
+    diag_duplicate_id,
+    diag_duplicate_label,
+    diag_unknown_tray_item,
+    diag_unknown_builtin,
+    diag_invalid_builtins_layout,
+    diag_invalid_tray_icon,
+    // Command line and console output
+    cli_missing_value,
+    cli_unknown_argument,
+    cli_unknown_command,
+    cli_too_many_arguments,
+    cli_run_needs_item,
+    cli_dump_needs_json,
+    cli_json_needs_dump,
+    cli_file_not_found,
+    cli_validate_ok,
+    cli_validate_problems,
+    cli_missing_translations,
+    cli_no_such_item,
+    cli_ambiguous_item,
+    cli_empty_command,
+    cli_list_empty_command,
+}
+
+// Localized Message | 번역 메시지
+// A key and its {0}, {1}, ... arguments, turned into text once the language is known
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub key: &'static str,
+    pub args: Vec<String>,
+}
+
+impl Message {
+    pub fn new(key: &'static str, args: &[&str]) -> Self {
+        Self {
+            key,
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    pub fn text(&self, strings: &LocalizedStrings) -> String {
+        let args: Vec<&str> = self.args.iter().map(String::as_str).collect();
+        fill(strings.get(self.key), &args)
+    }
 }
 
 impl LocalizedStrings {
-    pub fn new(locale: &str) -> Self {
-        match locale {
-            "en" => Self {
-                edit_environment: "Edit Environment".to_string(),
-                reload: "Reload Config".to_string(),
-                exit: "Exit".to_string(),
-                warning_title: "Warning".to_string(),
-                warning_msg: "Another instance is already running.".to_string(),
-            },
-            "ja" => Self {
-                edit_environment: "環境編集".to_string(),
-                reload: "設定再読み込み".to_string(),
-                exit: "終了".to_string(),
-                warning_title: "警告".to_string(),
-                warning_msg: "すでに実行中です。".to_string(),
-            },
-            _ => Self { // Default to ko
-                edit_environment: "환경 편집".to_string(),
-                reload: "환경 다시 읽기".to_string(),
-                exit: "종료".to_string(),
-                warning_title: "경고".to_string(),
-                warning_msg: "이미 실행 중입니다.".to_string(),
-            },
+    // Function: Load Strings | 문자열 로드
+    // `locales/<lang>.ini` in `locales_dir` overrides the built-in text for that language.
+    // Keys not found along the fallback chain are logged.
+    pub fn load(locale: &str, locales_dir: &Path) -> Self {
+        let chain = fallback_chain(locale);
+        let mut catalog: HashMap<String, String> = HashMap::new();
+        let mut translated: Option<HashSet<String>> = None; // Keys found before English
+        let mut sources: Vec<String> = Vec::new();
+
+        for lang in &chain {
+            if lang == FALLBACK_LOCALE {
+                translated = Some(catalog.keys().cloned().collect());
+            }
+            let file = locales_dir.join(format!("{}.ini", lang));
+            if let Ok(contents) = fs::read_to_string(&file) {
+                let user = parse_catalog(&contents);
+                for key in user.keys().filter(|k| !KEYS.contains(&k.as_str())) {
+                    crate::log_msg(
+                        "WARN",
+                        &format!("Unknown Translation Key in {}: {}", file.display(), key),
+                    );
+                }
+                sources.push(file.display().to_string());
+                merge(&mut catalog, user);
+            }
+            if let Some((_, text)) = EMBEDDED.iter().find(|(l, _)| l == lang) {
+                sources.push(format!("built-in {}", lang));
+                merge(&mut catalog, parse_catalog(text));
+            }
+        }
+
+        let translated = translated.unwrap_or_else(|| catalog.keys().cloned().collect());
+        if chain[0] != FALLBACK_LOCALE {
+            let missing = missing_keys(&translated);
+            if translated.is_empty() {
+                crate::log_msg(
+                    "WARN",
+                    &format!("No Translation for '{}', Using English", locale),
+                );
+            } else if !missing.is_empty() {
+                crate::log_msg(
+                    "WARN",
+                    &format!(
+                        "Translation '{}' Missing {} Key(s), Using English: {}",
+                        locale,
+                        missing.len(),
+                        missing.join(", ")
+                    ),
+                );
+            }
+        }
+        crate::log_msg(
+            "INFO",
+            &format!("Locale '{}' Loaded From: {}", locale, sources.join(", ")),
+        );
+        Self::from_catalog(&catalog)
+    }
+}
+
+// Function: Locales Folder | 번역 폴더
+// `locales` next to the config file
+pub fn locales_dir(ini_path: &Path) -> PathBuf {
+    ini_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("locales")
+}
+
+// Function: Resolve Locale | 사용할 언어 결정
+// `locale=auto` picks the closest available translation to the OS UI language
+pub fn resolve_locale(setting: &str, locales_dir: &Path) -> String {
+    if !setting.eq_ignore_ascii_case(AUTO_LOCALE) {
+        return setting.to_string();
+    }
+    let system = system_ui_language();
+    let available = available_locales(locales_dir);
+    let chosen = match &system {
+        Some(system) => match_locale(system, &available),
+        None => FALLBACK_LOCALE.to_string(),
+    };
+    crate::log_msg(
+        "INFO",
+        &format!(
+            "System UI Language: {}, Using Locale: {} (Available: {})",
+            system.as_deref().unwrap_or("unknown"),
+            chosen,
+            available.join(", ")
+        ),
+    );
+    chosen
+}
+
+// Function: Match Locale | 가까운 번역 찾기
+// First entry of the fallback chain that has a translation, e.g. "pt-BR" -> "pt"
+pub fn match_locale(locale: &str, available: &[String]) -> String {
+    fallback_chain(locale)
+        .into_iter()
+        .find(|l| available.contains(l))
+        .unwrap_or_else(|| FALLBACK_LOCALE.to_string())
+}
+
+// Function: Available Locales | 사용 가능한 번역 목록
+// Built-in languages plus every locales/<lang>.ini
+pub fn available_locales(locales_dir: &Path) -> Vec<String> {
+    let mut locales: Vec<String> = EMBEDDED.iter().map(|(l, _)| l.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(locales_dir) {
+        for path in entries.flatten().map(|e| e.path()) {
+            let is_ini = path
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("ini"));
+            if let Some(stem) = path.file_stem().filter(|_| is_ini) {
+                let lang = stem.to_string_lossy().to_lowercase().replace('_', "-");
+                if !locales.contains(&lang) {
+                    locales.push(lang);
+                }
+            }
+        }
+    }
+    locales
+}
+
+// Function: System UI Language | 시스템 표시 언어
+// BCP 47 name such as "ja-JP"
+pub fn system_ui_language() -> Option<String> {
+    use windows::Win32::Globalization::{GetUserDefaultUILanguage, LCIDToLocaleName};
+
+    let mut buffer = [0u16; 85]; // LOCALE_NAME_MAX_LENGTH
+    let len = unsafe { LCIDToLocaleName(GetUserDefaultUILanguage() as u32, Some(&mut buffer), 0) };
+    // The length includes the terminating NUL
+    (len > 1).then(|| String::from_utf16_lossy(&buffer[..len as usize - 1]))
+}
+
+// Function: Parent Chain | 상위 언어 순서
+// "pt-BR" -> ["pt-br", "pt"]
+pub fn parent_chain(locale: &str) -> Vec<String> {
+    let locale = locale.trim().to_lowercase().replace('_', "-");
+    let mut chain: Vec<String> = Vec::new();
+    let mut current = locale.as_str();
+    while !current.is_empty() {
+        chain.push(current.to_string());
+        current = current.rsplit_once('-').map_or("", |(parent, _)| parent);
+    }
+    chain
+}
+
+// Function: Fallback Chain | 대체 언어 순서
+// "pt-BR" -> ["pt-br", "pt", "en"]
+pub fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = parent_chain(locale);
+    if !chain.iter().any(|l| l == FALLBACK_LOCALE) {
+        chain.push(FALLBACK_LOCALE.to_string());
+    }
+    chain
+}
+
+// Function: Parse Translation File | 번역 파일 파싱
+// "key=value" lines; section headers and '#'/';' comments are ignored. "\n" is a line break.
+pub fn parse_catalog(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with(['#', ';', '[']))
+        .filter_map(|l| l.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().replace("\\n", "\n")))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+// Earlier layers win
+fn merge(catalog: &mut HashMap<String, String>, layer: HashMap<String, String>) {
+    for (key, value) in layer {
+        catalog.entry(key).or_insert(value);
+    }
+}
+
+// Function: Check Translations | 번역 검사
+// Every built-in language and locales/<lang>.ini with the keys it does not define
+pub fn check_translations(locales_dir: &Path) -> Vec<(String, Vec<&'static str>)> {
+    let mut sources: Vec<(String, String)> = EMBEDDED
+        .iter()
+        .map(|(lang, text)| (format!("built-in {}", lang), text.to_string()))
+        .collect();
+    if let Ok(entries) = fs::read_dir(locales_dir) {
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ini")) {
+                if let Ok(text) = fs::read_to_string(&path) {
+                    sources.push((path.display().to_string(), text));
+                }
+            }
+        }
+    }
+    sources
+        .into_iter()
+        .map(|(name, text)| {
+            let defined: HashSet<String> = parse_catalog(&text).into_keys().collect();
+            (name, missing_keys(&defined))
+        })
+        .filter(|(_, missing)| !missing.is_empty())
+        .collect()
+}
+
+// Function: Missing Keys | 누락된 키
+pub fn missing_keys(defined: &HashSet<String>) -> Vec<&'static str> {
+    KEYS.iter()
+        .copied()
+        .filter(|key| !defined.contains(*key))
+        .collect()
+}
+
+// Function: Fill Placeholders | 자리표시자 채우기
+// Replaces {0}, {1}, ... in a localized template
+pub fn fill(template: &str, args: &[&str]) -> String {
+    let mut result = template.to_string();
+    for (i, arg) in args.iter().enumerate() {
+        result = result.replace(&format!("{{{}}}", i), arg);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_builtin_locale_defines_every_key() {
+        for (lang, text) in EMBEDDED {
+            let defined: HashSet<String> = parse_catalog(text).into_keys().collect();
+            assert_eq!(missing_keys(&defined), Vec::<&str>::new(), "locale {}", lang);
         }
     }
+
+    #[test]
+    fn builtin_locales_have_no_unknown_keys() {
+        for (lang, text) in EMBEDDED {
+            let unknown: Vec<String> = parse_catalog(text)
+                .into_keys()
+                .filter(|k| !KEYS.contains(&k.as_str()))
+                .collect();
+            assert!(unknown.is_empty(), "locale {}: {:?}", lang, unknown);
+        }
+    }
+
+    #[test]
+    fn fallback_chain_ends_in_english() {
+        assert_eq!(fallback_chain("pt_BR"), vec!["pt-br", "pt", "en"]);
+        assert_eq!(fallback_chain("en-US"), vec!["en-us", "en"]);
+    }
+
+    #[test]
+    fn match_locale_uses_closest_parent() {
+        let available: Vec<String> = ["en", "ja", "pt"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(match_locale("ja-JP", &available), "ja");
+        assert_eq!(match_locale("pt-BR", &available), "pt");
+        assert_eq!(match_locale("de-DE", &available), "en");
+    }
+
+    #[test]
+    fn message_fills_placeholders() {
+        let strings = LocalizedStrings::load("en", Path::new("no-such-locales-dir"));
+        let message = Message::new("cli_validate_ok", &["QikMenu.ini", "3"]);
+        assert_eq!(message.text(&strings), "QikMenu.ini: OK (3 items)");
+    }
 }
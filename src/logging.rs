@@ -0,0 +1,585 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+use chrono::{DateTime, Local, NaiveDate};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// Target of QikMenu's own messages; other targets are dependencies | 로그 대상 이름
+pub const APP_TARGET: &str = "qikmenu";
+
+// Log file names: <date>.log, or <date>.jsonl for JSON lines | 로그 파일 이름
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const PLAIN_EXTENSION: &str = "log";
+const JSON_EXTENSION: &str = "jsonl";
+// Lines kept until [log] is applied; later ones are dropped | 설정 전 보관할 줄 수
+const PENDING_LIMIT: usize = 1000;
+
+// Log Level | 로그 수준
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "error" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+
+    pub fn level(self) -> Level {
+        match self {
+            LogLevel::Debug => Level::Debug,
+            LogLevel::Info => Level::Info,
+            LogLevel::Warn => Level::Warn,
+            LogLevel::Error => Level::Error,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+// Log Line Format | 로그 형식
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Plain, // [time] [LEVEL] message
+    Json,  // One JSON object per line
+}
+
+// Log Settings from [log] | [log] 로그 설정
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogSettings {
+    pub enabled: bool,
+    pub level: LogLevel,      // Lines below this level are dropped
+    pub dir: Option<PathBuf>, // Relative paths start at the executable; default <exe dir>/logs
+    pub retention_days: u32,  // 0 keeps logs forever
+    pub max_size_mb: u64,     // Total size of all log files, 0 for no limit
+    pub format: LogFormat,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            level: LogLevel::Info,
+            dir: None,
+            retention_days: 30,
+            max_size_mb: 100,
+            format: LogFormat::Plain,
+        }
+    }
+}
+
+// Settings in use and the folder they resolved to | 현재 로그 설정
+struct ActiveLog {
+    settings: LogSettings,
+    dir: Option<PathBuf>, // None while logging is disabled
+}
+
+static ACTIVE: Mutex<Option<ActiveLog>> = Mutex::new(None);
+// Writer thread for settings changes; lines go through the `log` facade
+static WRITER: Mutex<Option<Sender<LogCommand>>> = Mutex::new(None);
+
+// One Log Line | 로그 한 줄
+struct LogLine {
+    time: DateTime<Local>,
+    level: Level,
+    target: String,
+    message: String,
+}
+
+// Request to the Writer Thread | 로그 기록 스레드 요청
+enum LogCommand {
+    Line(LogLine),
+    Configure(LogSettings, Option<PathBuf>), // None disables logging
+    Flush(Sender<()>),
+}
+
+// `log` Facade Backend | log 크레이트 연결
+// Formatting and file access happen on the writer thread, so callers only send
+struct ChannelLogger {
+    tx: Sender<LogCommand>,
+}
+
+impl Log for ChannelLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let _ = self.tx.send(LogCommand::Line(LogLine {
+                time: Local::now(),
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            }));
+        }
+    }
+
+    // Waits until everything sent so far is on disk
+    fn flush(&self) {
+        let (done_tx, done_rx) = mpsc::channel();
+        if self.tx.send(LogCommand::Flush(done_tx)).is_ok() {
+            let _ = done_rx.recv();
+        }
+    }
+}
+
+// Flushes the log when main returns | main 종료 시 로그 기록 완료
+pub struct LogGuard;
+
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        flush();
+    }
+}
+
+// Function: Initialize Logging | 로그 초기화
+// Starts the writer thread. Lines are held in memory until the first `configure`,
+// so no folder is created or cleaned before [log] is known.
+// Keep the guard alive for the whole of main.
+pub fn init() -> LogGuard {
+    let (tx, rx) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name("log writer".to_string())
+        .spawn(move || Writer::new().run(rx));
+    if spawned.is_ok() {
+        *WRITER.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx.clone());
+        if log::set_boxed_logger(Box::new(ChannelLogger { tx })).is_ok() {
+            // Everything until [log] decides the level
+            log::set_max_level(LevelFilter::Debug);
+        }
+    }
+    LogGuard
+}
+
+// Function: Configure Logging | 로그 설정 적용
+// With enabled=false nothing on disk is touched: no folder, no cleanup
+pub fn configure(settings: &LogSettings) {
+    let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if active.as_ref().is_some_and(|a| &a.settings == settings) {
+        return;
+    }
+    log::set_max_level(max_level(settings));
+    let dir = settings
+        .enabled
+        .then(|| resolve_dir(settings.dir.as_deref()));
+    *active = Some(ActiveLog {
+        settings: settings.clone(),
+        dir: dir.clone(),
+    });
+    send(LogCommand::Configure(settings.clone(), dir));
+}
+
+// Function: Flush Logs | 로그 기록 완료
+// Call before std::process::exit, which skips destructors
+pub fn flush() {
+    log::logger().flush();
+}
+
+fn send(command: LogCommand) {
+    if let Some(tx) = WRITER.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        let _ = tx.send(command);
+    }
+}
+
+fn max_level(settings: &LogSettings) -> LevelFilter {
+    if settings.enabled {
+        settings.level.level().to_level_filter()
+    } else {
+        LevelFilter::Off
+    }
+}
+
+// Function: Log Directory | 로그 폴더
+// None until logging is configured, and while it is disabled
+pub fn log_dir() -> Option<PathBuf> {
+    ACTIVE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|a| a.dir.clone())
+}
+
+// Function: Resolve Log Directory | 로그 폴더 결정
+// Falls back to %LOCALAPPDATA%\QikMenu\logs when the folder cannot be written,
+// e.g. when QikMenu is installed under Program Files
+fn resolve_dir(configured: Option<&Path>) -> PathBuf {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."));
+    // Joining an absolute path replaces exe_dir
+    let preferred = exe_dir.join(configured.unwrap_or(Path::new("logs")));
+    if is_writable(&preferred) {
+        return preferred;
+    }
+    match std::env::var_os("LOCALAPPDATA") {
+        Some(local) => {
+            let fallback = PathBuf::from(local).join("QikMenu").join("logs");
+            if is_writable(&fallback) {
+                fallback
+            } else {
+                preferred
+            }
+        }
+        None => preferred,
+    }
+}
+
+fn is_writable(dir: &Path) -> bool {
+    if fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(".qikmenu-write-test");
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+    writable
+}
+
+// Function: Log File Name | 로그 파일 이름
+pub fn file_name(date: NaiveDate, format: LogFormat) -> String {
+    let extension = match format {
+        LogFormat::Plain => PLAIN_EXTENSION,
+        LogFormat::Json => JSON_EXTENSION,
+    };
+    format!("{}.{}", date.format(DATE_FORMAT), extension)
+}
+
+// Function: Parse Log File Name | 로그 파일 이름 해석
+// Returns the date for names written by `file_name`, None for anything else
+pub fn parse_file_name(name: &str) -> Option<NaiveDate> {
+    let (stem, extension) = name.rsplit_once('.')?;
+    if extension != PLAIN_EXTENSION && extension != JSON_EXTENSION {
+        return None;
+    }
+    // Require the exact zero-padded form so "2024-1-5.log" is left alone
+    let date = NaiveDate::parse_from_str(stem, DATE_FORMAT).ok()?;
+    (date.format(DATE_FORMAT).to_string() == stem).then_some(date)
+}
+
+// Function: Format Log Line | 로그 줄 만들기
+// Messages from dependencies are prefixed with their target, e.g. "tao::platform_impl"
+pub fn format_line(format: LogFormat, time: &str, level: &str, target: &str, msg: &str) -> String {
+    let own = target == APP_TARGET;
+    match format {
+        LogFormat::Plain if own => format!("[{}] [{}] {}\n", time, level, msg),
+        LogFormat::Plain => format!("[{}] [{}] {}: {}\n", time, level, target, msg),
+        LogFormat::Json => format!(
+            "{{\"time\": {}, \"level\": {}, \"target\": {}, \"message\": {}}}\n",
+            crate::json::json_str(time),
+            crate::json::json_str(level),
+            crate::json::json_str(target),
+            crate::json::json_str(msg)
+        ),
+    }
+}
+
+// Log Writer Thread State | 로그 기록 스레드 상태
+struct Writer {
+    active: Option<(LogSettings, PathBuf)>, // None until the first Configure, and while disabled
+    pending: Vec<LogLine>,     // Lines sent before the first Configure
+    disabled: bool,            // Lines are dropped instead of kept in `pending`
+    file: Option<(NaiveDate, BufWriter<File>)>, // Today's log, reopened when the date changes
+}
+
+impl Writer {
+    fn new() -> Self {
+        Self {
+            active: None,
+            pending: Vec::new(),
+            disabled: false,
+            file: None,
+        }
+    }
+
+    // Handles requests until every sender is gone, flushing after each burst
+    fn run(mut self, rx: Receiver<LogCommand>) {
+        while let Ok(command) = rx.recv() {
+            self.handle(command);
+            while let Ok(command) = rx.try_recv() {
+                self.handle(command);
+            }
+            self.flush();
+        }
+        self.flush();
+    }
+
+    fn handle(&mut self, command: LogCommand) {
+        match command {
+            LogCommand::Line(line) => self.write(line),
+            LogCommand::Configure(settings, dir) => {
+                self.flush();
+                self.file = None;
+                let Some(dir) = dir else {
+                    self.active = None;
+                    self.disabled = true;
+                    self.pending.clear();
+                    return;
+                };
+                clean_old_logs(&dir, &settings);
+                // Early lines follow the level that [log] asked for
+                let level = max_level(&settings);
+                self.active = Some((settings, dir));
+                self.disabled = false;
+                for line in std::mem::take(&mut self.pending) {
+                    if line.level <= level {
+                        self.write(line);
+                    }
+                }
+            }
+            LogCommand::Flush(done) => {
+                self.flush();
+                let _ = done.send(());
+            }
+        }
+    }
+
+    fn write(&mut self, line: LogLine) {
+        let Some((settings, dir)) = &self.active else {
+            if !self.disabled && self.pending.len() < PENDING_LIMIT {
+                self.pending.push(line);
+            }
+            return;
+        };
+        let date = line.time.date_naive();
+        // Daily rotation
+        if self.file.as_ref().is_none_or(|(d, _)| *d != date) {
+            if let Some((_, file)) = &mut self.file {
+                let _ = file.flush();
+            }
+            let path = dir.join(file_name(date, settings.format));
+            let opened = fs::OpenOptions::new().create(true).append(true).open(path);
+            let rotated = self.file.is_some();
+            self.file = opened.ok().map(|f| (date, BufWriter::new(f)));
+            if rotated {
+                clean_old_logs(dir, settings);
+            }
+        }
+        let line = format_line(
+            settings.format,
+            &line.time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            line.level.as_str(),
+            &line.target,
+            &line.message,
+        );
+        if let Some((_, file)) = &mut self.file {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some((_, file)) = &mut self.file {
+            let _ = file.flush();
+        }
+    }
+}
+
+// Function: Pick Logs to Delete | 삭제할 로그 고르기
+// `files` are (date, size) pairs. Logs older than `retention_days` go first, then the
+// oldest until the rest fits in `max_bytes`. Today's log is never deleted.
+pub fn files_to_delete(
+    files: &[(NaiveDate, u64)],
+    today: NaiveDate,
+    retention_days: u32,
+    max_bytes: u64,
+) -> Vec<usize> {
+    let mut newest_first: Vec<usize> = (0..files.len()).collect();
+    newest_first.sort_by_key(|&i| std::cmp::Reverse(files[i].0));
+
+    let mut total = 0;
+    let mut delete = Vec::new();
+    for i in newest_first {
+        let (date, size) = files[i];
+        let expired =
+            retention_days > 0 && (today - date).num_days() > i64::from(retention_days);
+        let over_size = max_bytes > 0 && date != today && total + size > max_bytes;
+        if expired || over_size {
+            delete.push(i);
+        } else {
+            total += size;
+        }
+    }
+    delete
+}
+
+// Function: Clean Old Logs | 오래된 로그 삭제
+// Only files named like QikMenu's own logs are deleted; other files in the folder stay
+fn clean_old_logs(dir: &Path, settings: &LogSettings) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let logs: Vec<(PathBuf, NaiveDate, u64)> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|e| {
+            let date = parse_file_name(&e.file_name().to_string_lossy())?;
+            let size = e.metadata().map(|m| m.len()).unwrap_or(0);
+            Some((e.path(), date, size))
+        })
+        .collect();
+
+    let files: Vec<(NaiveDate, u64)> = logs.iter().map(|(_, date, size)| (*date, *size)).collect();
+    let max_bytes = settings.max_size_mb.saturating_mul(1024 * 1024);
+    let today = Local::now().date_naive();
+    for i in files_to_delete(&files, today, settings.retention_days, max_bytes) {
+        let _ = fs::remove_file(&logs[i].0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "qikmenu-logs-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn line(level: Level, message: &str) -> LogCommand {
+        LogCommand::Line(LogLine {
+            time: Local::now(),
+            level,
+            target: APP_TARGET.to_string(),
+            message: message.to_string(),
+        })
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn retention_zero_keeps_old_files() {
+        let dir = temp_dir("keep");
+        fs::write(dir.join("2000-01-01.log"), b"old").unwrap();
+        let settings = LogSettings {
+            retention_days: 0,
+            max_size_mb: 0,
+            ..LogSettings::default()
+        };
+        clean_old_logs(&dir, &settings);
+        assert_eq!(names(&dir), vec!["2000-01-01.log"]);
+
+        clean_old_logs(&dir, &LogSettings::default());
+        assert!(names(&dir).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writer_waits_for_configure() {
+        let dir = temp_dir("pending");
+        fs::write(dir.join("2000-01-01.log"), b"old").unwrap();
+        let mut writer = Writer::new();
+        writer.handle(line(Level::Info, "early"));
+        writer.handle(line(Level::Debug, "too detailed"));
+        writer.flush();
+        // Nothing is written or cleaned before [log] is applied
+        assert_eq!(names(&dir), vec!["2000-01-01.log"]);
+
+        let settings = LogSettings {
+            retention_days: 0,
+            ..LogSettings::default()
+        };
+        writer.handle(LogCommand::Configure(settings, Some(dir.clone())));
+        writer.flush();
+        let today = file_name(Local::now().date_naive(), LogFormat::Plain);
+        assert_eq!(names(&dir), vec!["2000-01-01.log".to_string(), today.clone()]);
+        let text = fs::read_to_string(dir.join(today)).unwrap();
+        assert!(text.contains("early"));
+        assert!(!text.contains("too detailed"));
+        drop(writer);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn disabled_logging_touches_nothing() {
+        let dir = std::env::temp_dir().join(format!("qikmenu-logs-off-{}", std::process::id()));
+        let settings = LogSettings {
+            enabled: false,
+            dir: Some(dir.clone()),
+            ..LogSettings::default()
+        };
+        configure(&settings);
+        assert_eq!(log_dir(), None);
+        assert_eq!(log::max_level(), LevelFilter::Off);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn disabled_writer_drops_lines_and_keeps_files() {
+        let dir = temp_dir("writer-off");
+        fs::write(dir.join("2000-01-01.log"), b"old").unwrap();
+        let mut writer = Writer::new();
+        writer.handle(line(Level::Error, "before"));
+        let settings = LogSettings {
+            enabled: false,
+            ..LogSettings::default()
+        };
+        writer.handle(LogCommand::Configure(settings, None));
+        writer.handle(line(Level::Error, "after"));
+        assert!(writer.pending.is_empty());
+        assert_eq!(names(&dir), vec!["2000-01-01.log"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn files_to_delete_by_age_and_size() {
+        let today = date("2024-03-31");
+        let files = [
+            (date("2024-03-31"), 60),
+            (date("2024-03-30"), 30),
+            (date("2024-03-29"), 30),
+            (date("2024-01-01"), 1),
+        ];
+        assert_eq!(files_to_delete(&files, today, 30, 0), vec![3]);
+        assert_eq!(files_to_delete(&files, today, 0, 100), vec![2]);
+        assert!(files_to_delete(&files, today, 0, 0).is_empty());
+        // Today's log stays even when it alone is over the limit
+        assert_eq!(files_to_delete(&files, today, 0, 10), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn parse_file_name_accepts_only_own_logs() {
+        let name = file_name(date("2024-01-05"), LogFormat::Json);
+        assert_eq!(parse_file_name(&name), Some(date("2024-01-05")));
+        assert_eq!(parse_file_name("2024-1-5.log"), None);
+        assert_eq!(parse_file_name("2024-01-05.txt"), None);
+        assert_eq!(parse_file_name("notes.log"), None);
+    }
+}
@@ -2,13 +2,14 @@
 #![windows_subsystem = "windows"]
 
 use muda::{
-    ContextMenu, IsMenuItem, Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem, Submenu,
+    ContextMenu, IconMenuItem, IsMenuItem, Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem,
+    Submenu,
 };
 use std::collections::HashMap;
-use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
-use tao::event::{Event, WindowEvent};
+use tao::event::Event;
 use tao::event_loop::{ControlFlow, EventLoopBuilder};
 use tao::platform::windows::WindowExtWindows;
 use tao::window::WindowBuilder;
@@ -28,16 +29,53 @@ use windows::{
     Win32::UI::WindowsAndMessaging::*,
 };
 
+mod capture; // Output capture module | 실행 결과 수집 모듈
+mod cli; // Command-line arguments | 명령줄 인자 모듈
+mod config; // Config module | 환경 설정 모듈
+mod dispatch; // Event to action mapping | 이벤트 해석 모듈
+mod focus; // Focus-or-launch window matching | 실행 중인 창 찾기 모듈
+mod fuzzy; // Fuzzy ranking for the palette | 퍼지 검색 순위 모듈
+mod headless; // Subcommands without a window | 창 없는 하위 명령 모듈
+mod history; // Usage history | 사용 기록 모듈
+mod icons; // Menu item icons | 메뉴 아이콘 모듈
+mod ipc; // Commands from other instances | 인스턴스 간 통신 모듈
+mod json; // JSON text escaping | JSON 문자열 모듈
+mod launch; // Launch planner module | 실행 모듈
+mod logging; // Log settings and files | 로그 모듈
 mod localization; // Localization module | 번역 모듈
-use localization::LocalizedStrings;
+mod notify; // Tray notification module | 트레이 알림 모듈
+mod palette; // Search palette window | 검색 팔레트 창 모듈
+mod process_registry; // Launched process tracking | 실행한 프로세스 관리 모듈
+mod profile; // Profiles and instance locks | 프로필 모듈
+mod template; // {name} placeholder templates | 템플릿 모듈
+use capture::CaptureReport;
+use config::{load_config, AppEntry, Builtin, BuiltinMenu, Diagnostic};
+use dispatch::{Action, DispatchState, UserEvent};
+use focus::WindowMatcher;
+use history::{HistorySettings, HistoryStore, LaunchRecord};
+use icons::{IconCache, IconSpec};
+use ipc::IpcCommand;
+use launch::{plan_launch, LaunchError, LaunchMethod};
+use localization::{fill, LocalizedStrings};
+use logging::LogLevel;
+use notify::{Notifier, RecordingNotifier, Severity, TrayNotifier};
+use process_registry::{format_uptime, ProcessHandle, ProcessRegistry};
+use profile::Profile;
 
 use chrono::Local;
-use std::time::SystemTime;
 
 // Static Menu IDs | 고정 메뉴 ID
 const MENU_ID_EDIT: &str = "menu_edit_env";
 const MENU_ID_RELOAD: &str = "menu_reload";
 const MENU_ID_EXIT: &str = "menu_exit";
+const MENU_ID_OPEN_LOGS: &str = "menu_open_logs";
+const MENU_ID_OPEN_CONFIG_FOLDER: &str = "menu_open_config_folder";
+const MENU_ID_ABOUT: &str = "menu_about";
+// Dynamic Menu ID Prefixes (followed by a process or item key) | 동적 메뉴 ID 접두어
+const MENU_ID_RUNNING_FOCUS: &str = "menu_running_focus:";
+const MENU_ID_RUNNING_KILL: &str = "menu_running_kill:";
+const MENU_ID_RECENT: &str = "menu_recent:";
+const MENU_ID_APP: &str = "menu_app:";
 
 // App. Version History
 // - 260117a: 핫키를 파싱할 때 Alpha-numeric 문자는 match(switch)문을 이용하지 않고 계산을 통해 키코드를 알아내도록 변경.
@@ -47,48 +85,6 @@ const MENU_ID_EXIT: &str = "menu_exit";
 // - 251208b: 첫 릴리즈
 const APP_VERSION: &str = "260117a";
 
-// Function: Load Config | 환경 설정 로드 함수
-fn load_config(ini_path: &Path) -> (String, Vec<(String, String)>, String) {
-    let contents = fs::read_to_string(ini_path).unwrap_or_default();
-
-    let mut locale = "ko".to_string();
-    let mut current_section = "".to_string();
-    let mut hotkey = "".to_string();
-    let mut app_entries: Vec<(String, String)> = Vec::new();
-
-    for line in contents.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            current_section = trimmed[1..trimmed.len() - 1].to_lowercase();
-            continue;
-        }
-
-        if let Some((key, value)) = trimmed.split_once('=') {
-            let key = key.trim();
-            let value = value.trim();
-
-            if !key.is_empty() && !value.is_empty() {
-                if current_section == "global" {
-                    if key.eq_ignore_ascii_case("locale") {
-                        locale = value.to_lowercase();
-                    }
-                } else if current_section == "env" {
-                    if key.eq_ignore_ascii_case("hotkey") {
-                        hotkey = value.to_string();
-                    }
-                } else if current_section == "apps" {
-                    app_entries.push((key.to_string(), value.to_string()));
-                }
-            }
-        }
-    }
-    (locale, app_entries, hotkey)
-}
-
 // Function: Parse Hotkey String | 단축키 문자열 파싱
 fn parse_hotkey(hotkey_str: &str) -> Option<HotKey> {
     if hotkey_str.is_empty() {
@@ -294,148 +290,616 @@ unsafe fn force_window_foreground(hwnd: HWND) {
     // Not implementing yet, risky.
 }
 
+// Helper: Show Popup Menu at Cursor | 커서 위치에 팝업 메뉴 표시
+fn show_popup_menu(window: &tao::window::Window, menu: &Menu) {
+    unsafe {
+        window.set_visible(true);
+
+        let hwnd = HWND(window.hwnd() as _);
+        force_window_foreground(hwnd);
+
+        // Reset any stuck menu state (e.g. from Alt key)
+        SendMessageW(hwnd, WM_CANCELMODE, WPARAM(0), LPARAM(0));
+
+        log_msg("INFO", "Showing Menu...");
+        let _ = menu.show_context_menu_for_hwnd(window.hwnd() as isize, None);
+        log_msg("INFO", "Menu Closed (Event Loop Resuming)");
+
+        window.set_visible(false);
+
+        // Ensure we release focus/foreground cleanly (optional, but good practice)
+        // SetForegroundWindow(GetDesktopWindow());
+    }
+}
+
+// Tray Menu with lookup tables | 트레이 메뉴와 조회 테이블
+struct AppMenu {
+    menu: Menu,
+    admin: Menu, // Edit/Reload/Exit only, for tray clicks mapped to "admin"
+    locale: String, // Picks `label.<lang>=` for item labels
+    app_map: HashMap<String, AppEntry>, // Keyed by the item's stable ID
+    icons: HashMap<String, muda::Icon>,  // Item ID -> icon, reused by the Recent submenu
+    running: Submenu,        // Refilled by `refresh_running_menu`
+    recent: Option<Submenu>, // Refilled by `refresh_recent_menu`, None if history is off
+}
+
 // Function: Create Menu | 메뉴 생성 함수
 fn create_menu(
     locale: &str,
-    app_entries: &Vec<(String, String)>,
-) -> (Menu, HashMap<String, String>) {
+    strings: &LocalizedStrings,
+    app_entries: &[AppEntry],
+    history: &HistoryStore,
+    history_settings: &HistorySettings,
+    builtins: &BuiltinMenu,
+    icon_cache: &mut IconCache,
+) -> AppMenu {
     let menu = Menu::new();
-    let mut app_map: HashMap<String, String> = HashMap::new();
-    let strings = LocalizedStrings::new(locale);
+    let mut app_map: HashMap<String, AppEntry> = HashMap::new();
+    let mut icons: HashMap<String, muda::Icon> = HashMap::new();
+
+    if builtins.top && !builtins.items.is_empty() {
+        append_builtins(&menu, builtins, strings);
+        let _ = menu.append(&PredefinedMenuItem::separator());
+    }
 
-    // Add App Items
-    for (key, value) in app_entries {
-        let item = MenuItem::new(key, true, None);
-        let _ = menu.append(&item);
-        app_map.insert(item.id().as_ref().to_string(), value.clone());
+    // Frequency ordering keeps file order for ties
+    let mut ordered: Vec<&AppEntry> = app_entries.iter().collect();
+    if history_settings.enabled && history_settings.sort_frequent {
+        let counts = history.counts();
+        ordered.sort_by_key(|e| std::cmp::Reverse(counts.get(&e.id).copied().unwrap_or(0)));
+    }
+
+    // Add App Items, creating [apps/<Submenu>] submenus on first use
+    let mut submenus: HashMap<Vec<String>, Submenu> = HashMap::new();
+    for entry in ordered {
+        let icon = item_icon(entry, icon_cache);
+        let item = IconMenuItem::with_id(
+            MenuId::new(format!("{}{}", MENU_ID_APP, entry.id)),
+            entry.label_for(locale),
+            true,
+            icon.clone(),
+            None,
+        );
+        if let Some(icon) = icon {
+            icons.insert(entry.id.clone(), icon);
+        }
+        if entry.path.is_empty() {
+            let _ = menu.append(&item);
+        } else {
+            let _ = ensure_submenu(&menu, &mut submenus, &entry.path).append(&item);
+        }
+        app_map.insert(entry.id.clone(), entry.clone());
     }
 
     let _ = menu.append(&PredefinedMenuItem::separator());
 
-    // Static Items with fixed IDs
-    let edit_env_item = MenuItem::with_id(
-        MenuId::new(MENU_ID_EDIT),
-        &strings.edit_environment,
-        true,
-        None,
-    );
-    let _ = menu.append(&edit_env_item);
+    // Recently launched items
+    let recent = history_settings.enabled.then(|| {
+        let recent = Submenu::new(&strings.recent, false);
+        let _ = menu.append(&recent);
+        recent
+    });
+
+    // Processes launched by QikMenu
+    let running = Submenu::new(&strings.running, false);
+    let _ = menu.append(&running);
 
-    let reload_item = MenuItem::with_id(MenuId::new(MENU_ID_RELOAD), &strings.reload, true, None);
-    let _ = menu.append(&reload_item);
+    if !builtins.top {
+        append_builtins(&menu, builtins, strings);
+    }
 
-    let exit_item = MenuItem::with_id(MenuId::new(MENU_ID_EXIT), &strings.exit, true, None);
-    let _ = menu.append(&exit_item);
+    // The same items again; a muda item can only live in one menu
+    let admin = Menu::new();
+    for item in builtin_items(&builtins.items, strings) {
+        let _ = admin.append(&item);
+    }
 
-    (menu, app_map)
+    let app_menu = AppMenu {
+        menu,
+        admin,
+        locale: locale.to_string(),
+        app_map,
+        icons,
+        running,
+        recent,
+    };
+    refresh_recent_menu(&app_menu, history, history_settings);
+    app_menu
 }
 
-// Function: Get Log Directory | 로그 디렉토리 가져오기
-fn get_log_dir() -> PathBuf {
-    let current_dir = std::env::current_exe()
-        .unwrap_or_else(|_| PathBuf::from("."))
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .to_path_buf();
+// Function: Append Built-in Items | 기본 제공 메뉴 추가
+// Static items with fixed IDs, in [global] builtins= order, optionally in a submenu
+fn append_builtins(menu: &Menu, builtins: &BuiltinMenu, strings: &LocalizedStrings) {
+    let items = builtin_items(&builtins.items, strings);
+    if items.is_empty() {
+        return;
+    }
+    if builtins.submenu {
+        let submenu = Submenu::new("QikMenu", true);
+        for item in &items {
+            let _ = submenu.append(item);
+        }
+        let _ = menu.append(&submenu);
+    } else {
+        for item in &items {
+            let _ = menu.append(item);
+        }
+    }
+}
+
+// Function: Built-in Menu Items | 기본 제공 메뉴 항목
+fn builtin_items(builtins: &[Builtin], strings: &LocalizedStrings) -> Vec<MenuItem> {
+    builtins
+        .iter()
+        .map(|builtin| {
+            let (id, text) = match builtin {
+                Builtin::Edit => (MENU_ID_EDIT, &strings.edit_environment),
+                Builtin::Reload => (MENU_ID_RELOAD, &strings.reload),
+                Builtin::OpenLogs => (MENU_ID_OPEN_LOGS, &strings.open_logs),
+                Builtin::OpenConfigFolder => (MENU_ID_OPEN_CONFIG_FOLDER, &strings.open_config_folder),
+                Builtin::About => (MENU_ID_ABOUT, &strings.about),
+                Builtin::Exit => (MENU_ID_EXIT, &strings.exit),
+            };
+            MenuItem::with_id(MenuId::new(id), text, true, None)
+        })
+        .collect()
+}
+
+// Function: About Text | 정보 창 내용
+fn about_text(
+    strings: &LocalizedStrings,
+    ini_path: &Path,
+    hotkey: &str,
+    item_count: usize,
+    diagnostics: &[Diagnostic],
+) -> String {
+    let problems = if diagnostics.is_empty() {
+        strings.about_no_problems.clone()
+    } else {
+        let mut lines = vec![fill(&strings.about_problems, &[&diagnostics.len().to_string()])];
+        for d in diagnostics {
+            lines.push(format!("{}: {}", d.line, d.message(strings)));
+        }
+        lines.join("\n")
+    };
+    let hotkey = if hotkey.is_empty() {
+        strings.about_no_hotkey.as_str()
+    } else {
+        hotkey
+    };
+    fill(
+        &strings.about_text,
+        &[
+            APP_VERSION,
+            &ini_path.display().to_string(),
+            hotkey,
+            &item_count.to_string(),
+            &problems,
+        ],
+    )
+}
 
-    let logs_dir = current_dir.join("logs");
-    if !logs_dir.exists() {
-        let _ = fs::create_dir(&logs_dir);
+// Function: Item Icon | 항목 아이콘
+// `icon=` if set and loadable, otherwise a default for the kind of command
+fn item_icon(entry: &AppEntry, icon_cache: &mut IconCache) -> Option<muda::Icon> {
+    if let Some(value) = &entry.options.icon {
+        let spec = icon_cache.configured_spec(value);
+        let icon = icon_cache.get(&spec);
+        if icon.is_some() {
+            return icon;
+        }
+        log_msg(
+            "WARN",
+            &format!("Icon Load Failed: {} ({})", value, entry.label),
+        );
     }
-    logs_dir
+    let spec = icon_cache.default_spec(&entry.command);
+    icon_cache.get(&spec)
+}
+
+// Function: Ensure Submenu Path | 하위 메뉴 경로 생성
+// Creates every missing level of `path` and returns the innermost submenu
+fn ensure_submenu<'a>(
+    menu: &Menu,
+    submenus: &'a mut HashMap<Vec<String>, Submenu>,
+    path: &[String],
+) -> &'a Submenu {
+    for depth in 1..=path.len() {
+        let key = path[..depth].to_vec();
+        if submenus.contains_key(&key) {
+            continue;
+        }
+        let submenu = Submenu::new(&path[depth - 1], true);
+        if depth == 1 {
+            let _ = menu.append(&submenu);
+        } else if let Some(parent) = submenus.get(&path[..depth - 1]) {
+            let _ = parent.append(&submenu);
+        }
+        submenus.insert(key, submenu);
+    }
+    &submenus[path]
+}
+
+// Function: Refresh Recent Submenu | 최근 실행 메뉴 갱신
+// Items removed from the config are skipped; renamed ones show their current label
+fn refresh_recent_menu(app_menu: &AppMenu, history: &HistoryStore, settings: &HistorySettings) {
+    let Some(recent) = &app_menu.recent else {
+        return;
+    };
+    while recent.remove_at(0).is_some() {}
+    for key in history.recent_keys(settings.recent_count) {
+        if let Some(entry) = app_menu.app_map.get(&key) {
+            let item = IconMenuItem::with_id(
+                MenuId::new(format!("{}{}", MENU_ID_RECENT, key)),
+                entry.label_for(&app_menu.locale),
+                true,
+                app_menu.icons.get(&key).cloned(),
+                None,
+            );
+            let _ = recent.append(&item);
+        }
+    }
+    recent.set_enabled(!recent.items().is_empty());
+}
+
+// Function: Refresh Running Submenu | 실행 중 메뉴 갱신
+// Prunes finished processes and lists the rest with uptime and Focus/Terminate actions
+fn refresh_running_menu(
+    running: &Submenu,
+    registry: &mut ProcessRegistry,
+    strings: &LocalizedStrings,
+) {
+    for label in registry.prune() {
+        log_msg("INFO", &format!("Process Exited: {}", label));
+    }
+
+    while running.remove_at(0).is_some() {}
+    for p in registry.list() {
+        let text = format!("{} ({})", p.label, format_uptime(p.uptime()));
+        let focus_item = MenuItem::with_id(
+            MenuId::new(format!("{}{}", MENU_ID_RUNNING_FOCUS, p.key)),
+            &strings.focus,
+            true,
+            None,
+        );
+        let kill_item = MenuItem::with_id(
+            MenuId::new(format!("{}{}", MENU_ID_RUNNING_KILL, p.key)),
+            &strings.terminate,
+            true,
+            None,
+        );
+        let process_menu = Submenu::new(text, true);
+        let _ = process_menu.append_items(&[&focus_item, &kill_item]);
+        let _ = running.append(&process_menu);
+    }
+    running.set_enabled(!registry.list().is_empty());
 }
 
 // Function: Write Log Message | 로그 메시지 기록
+// Queued for the writer thread; level, folder and format come from [log], see `logging`
 fn log_msg(level: &str, msg: &str) {
-    let now = Local::now();
-    let date_str = now.format("%Y-%m-%d").to_string();
-    let time_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    let level = LogLevel::parse(level).unwrap_or(LogLevel::Info).level();
+    log::log!(target: logging::APP_TARGET, level, "{}", msg);
+}
 
-    let logs_dir = get_log_dir();
-    let log_file_path = logs_dir.join(format!("{}.log", date_str));
+// Function: Run Menu Entry | 메뉴 항목 실행
+// Returns true if the item was launched or focused
+fn run_entry(
+    entry: &AppEntry,
+    strings: &LocalizedStrings,
+    notifier: &dyn Notifier,
+    capture_tx: &Sender<CaptureReport>,
+    registry: &mut ProcessRegistry,
+    dry_run: bool,
+) -> Option<bool> {
+    log_msg("INFO", &format!("Executing Command: {}", entry.command));
+    let Some(plan) = plan_launch(&entry.command, &entry.options) else {
+        return Some(false);
+    };
+
+    // Dry run: report what would be launched and start nothing
+    if dry_run {
+        log_msg("INFO", &format!("Dry Run: {}: {}", entry.label, launch::describe(&plan)));
+        notifier.notify_always(
+            Severity::Info,
+            "QikMenu",
+            &fill(&strings.dry_run, &[&entry.label, &launch::summary(&plan)]),
+        );
+        return None;
+    }
 
-    let log_entry = format!("[{}] [{}] {}\n", time_str, level, msg);
+    // Focus-or-launch: bring an existing window to front instead of starting another copy
+    if entry.options.single {
+        let matcher = WindowMatcher::for_item(&entry.options, &plan.program);
+        if let Some(hwnd) = focus::find_window(&matcher) {
+            log_msg("INFO", &format!("Focusing Existing Window: {}", entry.label));
+            focus::activate_window(hwnd);
+            return Some(true);
+        }
+        log_msg("INFO", "No Existing Window Found. Launching...");
+    }
 
-    // Append to file
-    use std::io::Write;
-    if let Ok(mut file) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_file_path)
-    {
-        let _ = file.write_all(log_entry.as_bytes());
+    if plan.method == LaunchMethod::Elevated {
+        log_msg("INFO", "Requesting Elevation (runas)...");
+        if entry.options.capture {
+            log_msg("WARN", "Capture is not available for elevated items.");
+        }
     }
+    let success = match launch::execute(&plan) {
+        Ok(Some(ProcessHandle::Child(child))) if plan.capture => {
+            log_msg("INFO", "Execution Started (Capturing Output).");
+            registry.register(&entry.label, child.id(), None);
+            capture::watch(entry.label.clone(), child, capture_tx.clone());
+            true
+        }
+        Ok(Some(handle)) => {
+            log_msg("INFO", "Execution Triggered Successfully.");
+            registry.register(&entry.label, handle.id(), Some(handle));
+            true
+        }
+        Ok(None) => {
+            // Handed to an already running program, nothing to track
+            log_msg("INFO", "Execution Triggered Successfully (No New Process).");
+            true
+        }
+        Err(LaunchError::Cancelled) => {
+            log_msg("WARN", "Elevation Cancelled by User (UAC).");
+            notifier.notify(
+                Severity::Info,
+                "QikMenu",
+                &fill(&strings.elevation_cancelled, &[&entry.label]),
+            );
+            false
+        }
+        Err(e) => {
+            let err_msg = format!("Execution Failed: {}", e);
+            eprintln!("{}", err_msg);
+            log_msg("ERROR", &err_msg);
+            notifier.notify(
+                Severity::Error,
+                "QikMenu",
+                &fill(&strings.exec_failed, &[&entry.label, &e.to_string()]),
+            );
+            false
+        }
+    };
+    Some(success)
 }
 
-// Function: Clean Old Logs (Older than 30 days) | 오래된 로그 삭제
-fn clean_old_logs() {
-    let logs_dir = get_log_dir();
-    let now = SystemTime::now();
-    let max_age = std::time::Duration::from_secs(30 * 24 * 60 * 60); // 30 days
-
-    if let Ok(entries) = fs::read_dir(logs_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() {
-                if let Ok(metadata) = fs::metadata(&path) {
-                    if let Ok(modified) = metadata.modified() {
-                        if let Ok(age) = now.duration_since(modified) {
-                            if age > max_age {
-                                let _ = fs::remove_file(path);
-                            }
-                        }
-                    }
-                }
-            }
+// Function: Load Tray Icon | 트레이 아이콘 로드
+// `tray_icon=` relative to the config folder; falls back to the embedded icon with a diagnostic
+fn load_tray_icon(
+    ini_path: &Path,
+    setting: Option<&(usize, String)>,
+) -> (tray_icon::Icon, Option<Diagnostic>) {
+    let mut problem = None;
+    if let Some((line, value)) = setting {
+        let config_dir = ini_path.parent().unwrap_or(Path::new("."));
+        let spec = IconSpec::parse(value).relative_to(config_dir);
+        let icon = icons::load_image(&spec, 32).and_then(|image| {
+            let (width, height) = image.dimensions();
+            tray_icon::Icon::from_rgba(image.into_raw(), width, height).ok()
+        });
+        if let Some(icon) = icon {
+            return (icon, None);
         }
+        problem = Some(Diagnostic::new(*line, "diag_invalid_tray_icon", &[value]));
     }
+
+    let icon_bytes = include_bytes!("../assets/tray_icon.png");
+    let icon_image = image::load_from_memory(icon_bytes)
+        .expect("Failed to load icon")
+        .into_rgba8();
+    let (width, height) = icon_image.dimensions();
+    let rgba = icon_image.into_raw();
+    let icon = tray_icon::Icon::from_rgba(rgba, width, height).expect("Failed to create icon");
+    (icon, problem)
 }
 
-// Function: Configure Command | 명령어 파싱 함수
-// Splits string by spaces but respects quotes
-fn parse_cmd(input: &str) -> Vec<String> {
-    let mut args = Vec::new();
-    let mut current = String::new();
-    let mut in_quotes = false;
-
-    for c in input.chars() {
-        if c == '"' {
-            in_quotes = !in_quotes;
-        } else if c == ' ' && !in_quotes {
-            if !current.is_empty() {
-                args.push(current.clone());
-                current.clear();
-            }
-        } else {
-            current.push(c);
+// Function: Render Tooltip | 툴팁 만들기
+// Windows limits tray tooltips to 127 characters
+fn render_tooltip(template: &str, profile: &Profile, hotkey: &str, count: usize) -> String {
+    let count = count.to_string();
+    let text = template::render(
+        template,
+        &[
+            ("profile", profile.display_name()),
+            ("hotkey", hotkey),
+            ("count", &count),
+        ],
+    );
+    text.chars().take(127).collect()
+}
+
+// Function: Register Hotkey | 단축키 등록
+// Unregisters `old` first. Returns the registered hotkey, or None if empty or failed.
+fn register_hotkey(
+    manager: &GlobalHotKeyManager,
+    old: Option<HotKey>,
+    hotkey_str: &str,
+    strings: &LocalizedStrings,
+    notifier: &dyn Notifier,
+) -> Option<HotKey> {
+    if let Some(hk) = old {
+        let _ = manager.unregister(hk);
+    }
+    let hk = parse_hotkey(hotkey_str)?;
+    match manager.register(hk) {
+        Ok(()) => {
+            log_msg("INFO", &format!("Hotkey registered: {}", hotkey_str));
+            Some(hk)
+        }
+        Err(e) => {
+            log_msg("ERROR", &format!("Failed to register hotkey: {}", e));
+            notifier.notify(
+                Severity::Error,
+                "QikMenu",
+                &fill(&strings.hotkey_failed, &[hotkey_str]),
+            );
+            None
         }
     }
-    if !current.is_empty() {
-        args.push(current);
+}
+
+// Function: Record Launch in History | 실행 기록 저장
+fn record_history(
+    history: &mut HistoryStore,
+    settings: &HistorySettings,
+    entry: &AppEntry,
+    success: bool,
+) {
+    if settings.enabled {
+        history.record(
+            LaunchRecord {
+                timestamp: Local::now().timestamp(),
+                key: entry.id.clone(),
+                label: entry.label.clone(),
+                success,
+            },
+            settings,
+        );
+    }
+}
+
+// Function: Report Captured Process | 실행 결과 보고
+// Logs the exit code and output tail; a failure is also shown as a notification
+fn report_capture(report: &CaptureReport, strings: &LocalizedStrings, notifier: &dyn Notifier) {
+    let exit_code = report
+        .exit_code
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    let summary = format!(
+        "Process Finished: {} (Exit Code: {}, Duration: {:.1}s)",
+        report.label,
+        exit_code,
+        report.duration.as_secs_f64()
+    );
+    log_msg(if report.success() { "INFO" } else { "ERROR" }, &summary);
+    if !report.stdout_tail.is_empty() {
+        log_msg("INFO", &format!("[stdout]\n{}", report.stdout_tail));
+    }
+    if !report.stderr_tail.is_empty() {
+        log_msg("INFO", &format!("[stderr]\n{}", report.stderr_tail));
+    }
+    if !report.success() {
+        let detail = if report.stderr_tail.is_empty() {
+            &report.stdout_tail
+        } else {
+            &report.stderr_tail
+        };
+        let body = format!(
+            "{}\n{}",
+            fill(&strings.process_failed, &[&report.label, &exit_code]),
+            capture::tail_lines(detail, 3)
+        );
+        notifier.notify(Severity::Error, &report.label, body.trim_end());
+    }
+}
+
+// Function: Report Config Diagnostics | 환경 설정 문제 보고
+fn report_diagnostics(
+    diagnostics: &[Diagnostic],
+    strings: &LocalizedStrings,
+    notifier: &dyn Notifier,
+) {
+    for d in diagnostics {
+        log_msg("WARN", &format!("Config Line {}: {}", d.line, d.message(strings)));
+    }
+    if let Some(first) = diagnostics.first() {
+        let msg = fill(
+            &strings.config_problems,
+            &[
+                &diagnostics.len().to_string(),
+                &first.line.to_string(),
+                &first.message(strings),
+            ],
+        );
+        notifier.notify(Severity::Warning, "QikMenu", &msg);
     }
-    args
 }
 
 fn main() {
+    let _log_guard = logging::init();
+
+    // 4. Resolve INI Path
+    // Look for QikMenu.ini (or QikMenu-<profile>.ini) in the same directory as the executable
+    let exe_path = std::env::current_exe().unwrap_or_else(|_| ".".into());
+    let exe_dir = exe_path.parent().unwrap_or_else(|| Path::new("."));
+    let current_dir = std::env::current_dir().unwrap_or_else(|_| exe_dir.to_path_buf());
+    let args = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            // Arguments are unusable, so the default config picks the log folder and language
+            let default_profile = Profile::resolve(&cli::CliArgs::default(), exe_dir, &current_dir);
+            logging::configure(&load_config(&default_profile.ini_path).log);
+            let msg = e.text(&headless::strings_for_path(&default_profile.ini_path));
+            log_msg("ERROR", &format!("Invalid Arguments: {}", msg));
+            if headless::attach_parent_console() {
+                eprintln!("QikMenu: {}", msg);
+            } else {
+                unsafe {
+                    MessageBoxW(None, &HSTRING::from(msg), w!("QikMenu"), MB_OK | MB_ICONERROR);
+                }
+            }
+            logging::flush();
+            std::process::exit(headless::EXIT_USAGE);
+        }
+    };
+    let profile = Profile::resolve(&args, exe_dir, &current_dir);
+    let ini_path = profile.ini_path.clone();
+
     // 1. Logging Initialization
-    clean_old_logs();
+    // [log] is read first so every later line goes to the configured place
+    let config = load_config(&ini_path);
+    logging::configure(&config.log);
     log_msg(
         "INFO",
         &format!("Application Started. Version: {}", APP_VERSION),
     );
+    if let Some(dir) = logging::log_dir() {
+        log_msg("INFO", &format!("Log Folder: {}", dir.display()));
+    }
+    log_msg(
+        "INFO",
+        &format!(
+            "Profile: {}, Config: {}",
+            profile.display_name(),
+            ini_path.display()
+        ),
+    );
 
-    let event_loop = EventLoopBuilder::new().build();
+    // Headless subcommands exit before any window or event loop exists
+    if let Some(subcommand) = &args.subcommand {
+        headless::attach_parent_console();
+        let code = headless::run(subcommand, &ini_path, args.dry_run);
+        log_msg("INFO", &format!("Subcommand Finished. Exit Code: {}", code));
+        logging::flush();
+        std::process::exit(code);
+    }
 
-    // 4. Resolve INI Path
-    // Look for QikMenu.ini in the same directory as the executable
-    let ini_path = std::env::current_exe()
-        .unwrap_or_else(|_| ".".into())
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .join("QikMenu.ini");
+    let event_loop = EventLoopBuilder::<UserEvent>::with_user_event().build();
 
     // Initial Load
-    let (mut locale, mut app_entries, mut hotkey_str) = load_config(&ini_path);
+    let mut locale = localization::resolve_locale(&config.locale, &localization::locales_dir(&ini_path));
+    let mut app_entries = config.app_entries;
+    let mut hotkey_str = config.hotkey;
+    let mut palette_hotkey_str = config.palette_hotkey;
+    let mut editor = config.editor;
+    let mut dry_run = args.dry_run || config.dry_run;
+    // Menus are shown by the event loop, not attached to the tray icon
+    let mut tray_clicks = config.tray_clicks.clone();
+    if dry_run {
+        log_msg("INFO", "Dry Run Mode: launches are logged, nothing is started.");
+    }
+    let mut strings = LocalizedStrings::load(&locale, &localization::locales_dir(&ini_path));
+    // Notifications raised before the tray icon exists are shown once it is created
+    let startup_notices = RecordingNotifier::default();
+    let (tray_image, tray_problem) = load_tray_icon(&ini_path, config.tray_icon.as_ref());
+    // Kept for the About box
+    let mut load_diagnostics = config.diagnostics.clone();
+    load_diagnostics.extend(tray_problem);
+    load_diagnostics.sort_by_key(|d| d.line);
+    report_diagnostics(&load_diagnostics, &strings, &startup_notices);
     log_msg(
         "INFO",
         &format!(
@@ -446,10 +910,26 @@ fn main() {
     );
 
     // Check Single Instance
-    let instance = SingleInstance::new("QikMenu_Lock").unwrap();
+    let instance = SingleInstance::new(&profile.lock_name()).unwrap();
+    let endpoint = ipc::endpoint_name(&profile.instance_key());
     if !instance.is_single() {
+        // Hand the request to the running instance; plain launches open its menu
+        let command = args.command.clone().unwrap_or(IpcCommand::Show);
+        unsafe {
+            // Let the running instance bring its menu to the front
+            let _ = AllowSetForegroundWindow(ASFW_ANY);
+        }
+        match ipc::send(&endpoint, &command) {
+            Ok(()) => {
+                log_msg(
+                    "INFO",
+                    &format!("Forwarded to Running Instance: {}", command.to_line()),
+                );
+                return;
+            }
+            Err(e) => log_msg("ERROR", &format!("IPC Send Failed: {}", e)),
+        }
         log_msg("WARN", "Another instance is already running.");
-        let strings = LocalizedStrings::new(&locale);
         unsafe {
             let title_h = HSTRING::from(&strings.warning_title);
             let msg_h = HSTRING::from(&strings.warning_msg);
@@ -457,6 +937,19 @@ fn main() {
         }
         return;
     }
+    if args.command == Some(IpcCommand::Exit) {
+        log_msg("INFO", "No Running Instance to Exit.");
+        return;
+    }
+
+    // Commands from later instances; our own command-line request goes first
+    let (ipc_tx, ipc_rx) = std::sync::mpsc::channel::<IpcCommand>();
+    if let Err(e) = ipc::serve(&endpoint, ipc_tx.clone()) {
+        log_msg("ERROR", &format!("IPC Listen Failed: {}", e));
+    }
+    if let Some(command) = args.command.clone() {
+        let _ = ipc_tx.send(command);
+    }
 
     let window = WindowBuilder::new()
         .with_visible(false)
@@ -468,153 +961,434 @@ fn main() {
 
     // 5. Setup Hotkey
     let hotkey_manager = GlobalHotKeyManager::new().unwrap();
-    let mut current_hotkey: Option<HotKey> = parse_hotkey(&hotkey_str);
-
-    if let Some(hk) = current_hotkey {
-        if let Err(e) = hotkey_manager.register(hk) {
-            log_msg("ERROR", &format!("Failed to register hotkey: {}", e));
-        } else {
-            log_msg("INFO", &format!("Hotkey registered: {}", hotkey_str));
-        }
-    }
+    let mut current_hotkey = register_hotkey(
+        &hotkey_manager,
+        None,
+        &hotkey_str,
+        &strings,
+        &startup_notices,
+    );
+    let mut palette_hotkey = register_hotkey(
+        &hotkey_manager,
+        None,
+        &palette_hotkey_str,
+        &strings,
+        &startup_notices,
+    );
 
     // Build initial menu
-    let (mut menu, mut app_map) = create_menu(&locale, &app_entries);
+    let mut history_settings = config.history.clone();
+    let mut history = HistoryStore::load(
+        &ini_path.with_extension("history"),
+        &history_settings,
+        Local::now().timestamp(),
+    );
+    let mut icon_cache = IconCache::new(ini_path.parent().unwrap_or(Path::new(".")));
+    let mut app_menu = create_menu(
+        &locale,
+        &strings,
+        &app_entries,
+        &history,
+        &history_settings,
+        &config.builtins,
+        &mut icon_cache,
+    );
+    let mut registry = ProcessRegistry::default();
 
     // 6. Create Tray Icon | 트레이 아이콘 생성
-    // Load Icon
-    let icon_bytes = include_bytes!("../assets/tray_icon.png");
-    let icon_image = image::load_from_memory(icon_bytes)
-        .expect("Failed to load icon")
-        .into_rgba8();
-    let (width, height) = icon_image.dimensions();
-    let rgba = icon_image.into_raw();
-
     let tray_icon = TrayIconBuilder::new()
-        .with_tooltip("QikMenu")
-        .with_icon(tray_icon::Icon::from_rgba(rgba, width, height).expect("Failed to create icon"))
-        .with_menu(Box::new(menu.clone()))
+        .with_tooltip(render_tooltip(
+            &config.tooltip,
+            &profile,
+            &hotkey_str,
+            app_entries.len(),
+        ))
+        .with_icon(tray_image)
         .build()
         .unwrap();
 
-    let menu_channel = MenuEvent::receiver();
-    let tray_channel = TrayIconEvent::receiver();
-    let hotkey_channel = GlobalHotKeyEvent::receiver();
-
-    event_loop.run(move |_event, _, control_flow| {
-        // Poll every 50ms to check channels (Hotkeys, Tray, Menu)
-        // This is necessary because these channels do not wake up the TAO event loop on their own.
-        *control_flow = ControlFlow::WaitUntil(
-            std::time::Instant::now() + std::time::Duration::from_millis(50),
-        );
+    let mut notifier = TrayNotifier::new(config.notify_level);
+    startup_notices.replay(&notifier);
+
+    // Forward every event source into the event loop so it can sleep until something happens
+    let proxy = event_loop.create_proxy();
+    MenuEvent::set_event_handler(Some(move |event| {
+        let _ = proxy.send_event(UserEvent::Menu(event));
+    }));
+    let proxy = event_loop.create_proxy();
+    TrayIconEvent::set_event_handler(Some(move |event| {
+        let _ = proxy.send_event(UserEvent::Tray(event));
+    }));
+    let proxy = event_loop.create_proxy();
+    GlobalHotKeyEvent::set_event_handler(Some(move |event| {
+        let _ = proxy.send_event(UserEvent::HotKey(event));
+    }));
+    let proxy = event_loop.create_proxy();
+    palette::set_selection_handler(move |id| {
+        let _ = proxy.send_event(UserEvent::PaletteSelected(id));
+    });
+    let (capture_tx, capture_rx) = std::sync::mpsc::channel::<CaptureReport>();
+    let proxy = event_loop.create_proxy();
+    std::thread::spawn(move || {
+        for report in capture_rx {
+            let _ = proxy.send_event(UserEvent::Capture(report));
+        }
+    });
+    let proxy = event_loop.create_proxy();
+    std::thread::spawn(move || {
+        for command in ipc_rx {
+            let _ = proxy.send_event(UserEvent::Ipc(command));
+        }
+    });
+    let timer_proxy = event_loop.create_proxy();
+    let mut tray_click = dispatch::TrayClickState::default();
 
-        if let Ok(event) = menu_channel.try_recv() {
-            let id = event.id.as_ref();
-            log_msg("INFO", &format!("Menu Item Clicked: {}", id));
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Wait;
 
-            if id == MENU_ID_EDIT {
-                let _ = open::that(&ini_path);
-            } else if id == MENU_ID_RELOAD {
+        let Event::UserEvent(event) = event else {
+            return;
+        };
+        if let UserEvent::Ipc(command) = &event {
+            log_msg("INFO", &format!("IPC Command: {}", command.to_line()));
+        }
+        if let UserEvent::Menu(menu_event) = &event {
+            log_msg("INFO", &format!("Menu Item Clicked: {}", menu_event.id.as_ref()));
+        }
+        let mut state = DispatchState {
+            menu_hotkey: current_hotkey.map(|hk| hk.id()),
+            palette_hotkey: palette_hotkey.map(|hk| hk.id()),
+            tray: tray_clicks.clone(),
+            tray_click,
+        };
+        let action = dispatch::dispatch(&mut state, event);
+        tray_click = state.tray_click;
+        let Some(action) = action else {
+            return;
+        };
+
+        match action {
+            Action::EditConfig => {
+                // Jump to the first problem of the last load
+                let line = load_diagnostics.first().map_or(1, |d| d.line);
+                let opened = match launch::plan_editor(&editor, &ini_path, line) {
+                    Some(plan) => {
+                        log_msg("INFO", &format!("Opening Editor: {}", launch::summary(&plan)));
+                        launch::execute(&plan).map(|_| ()).map_err(|e| {
+                            log_msg("ERROR", &format!("Editor Failed: {}", e));
+                            notifier.notify(
+                                Severity::Error,
+                                "QikMenu",
+                                &fill(&strings.exec_failed, &[&plan.program, &e.to_string()]),
+                            );
+                        })
+                    }
+                    None => Err(()),
+                };
+                // No editor= or it failed: use the .ini file association
+                if opened.is_err() {
+                    let _ = open::that(&ini_path);
+                }
+            }
+            Action::OpenLogs => {
+                // No folder while [log] enabled=false
+                if let Some(dir) = logging::log_dir() {
+                    let _ = open::that(dir);
+                }
+            }
+            Action::OpenConfigFolder => {
+                if let Some(dir) = ini_path.parent() {
+                    let _ = open::that(dir);
+                }
+            }
+            Action::ShowAbout => {
+                let text = about_text(
+                    &strings,
+                    &ini_path,
+                    &hotkey_str,
+                    app_entries.len(),
+                    &load_diagnostics,
+                );
+                unsafe {
+                    MessageBoxW(
+                        None,
+                        &HSTRING::from(text),
+                        &HSTRING::from(&strings.about),
+                        MB_OK | MB_ICONINFORMATION,
+                    );
+                }
+            }
+            Action::Reload => {
                 // Reload Logic
                 log_msg("INFO", "Reloading Configuration...");
-                let (new_locale, new_app_entries, new_hotkey_str) = load_config(&ini_path);
-                let (new_menu, new_map) = create_menu(&new_locale, &new_app_entries);
+                let new_config = load_config(&ini_path);
+                logging::configure(&new_config.log);
+                history_settings = new_config.history.clone();
+                history.apply_settings(&history_settings, Local::now().timestamp());
+                let new_locale = localization::resolve_locale(
+                    &new_config.locale,
+                    &localization::locales_dir(&ini_path),
+                );
+                strings = LocalizedStrings::load(&new_locale, &localization::locales_dir(&ini_path));
+                let new_menu = create_menu(
+                    &new_locale,
+                    &strings,
+                    &new_config.app_entries,
+                    &history,
+                    &history_settings,
+                    &new_config.builtins,
+                    &mut icon_cache,
+                );
+                notifier.min_severity = new_config.notify_level;
+                dry_run = args.dry_run || new_config.dry_run;
+                let (tray_image, tray_problem) =
+                    load_tray_icon(&ini_path, new_config.tray_icon.as_ref());
+                load_diagnostics = new_config.diagnostics.clone();
+                load_diagnostics.extend(tray_problem);
+                load_diagnostics.sort_by_key(|d| d.line);
+                report_diagnostics(&load_diagnostics, &strings, &notifier);
+                let _ = tray_icon.set_icon(Some(tray_image));
+                let _ = tray_icon.set_tooltip(Some(render_tooltip(
+                    &new_config.tooltip,
+                    &profile,
+                    &new_config.hotkey,
+                    new_config.app_entries.len(),
+                )));
 
                 // Update Hotkey
+                let new_hotkey_str = new_config.hotkey;
                 if new_hotkey_str != hotkey_str {
-                    if let Some(hk) = current_hotkey {
-                        let _ = hotkey_manager.unregister(hk);
-                    }
-                    current_hotkey = parse_hotkey(&new_hotkey_str);
-                    if let Some(hk) = current_hotkey {
-                        if let Err(e) = hotkey_manager.register(hk) {
-                            log_msg("ERROR", &format!("Failed to register new hotkey: {}", e));
-                        } else {
-                            log_msg(
-                                "INFO",
-                                &format!("New hotkey registered: {}", new_hotkey_str),
-                            );
-                        }
-                    }
+                    current_hotkey = register_hotkey(
+                        &hotkey_manager,
+                        current_hotkey,
+                        &new_hotkey_str,
+                        &strings,
+                        &notifier,
+                    );
                     hotkey_str = new_hotkey_str;
                 }
+                if new_config.palette_hotkey != palette_hotkey_str {
+                    palette_hotkey = register_hotkey(
+                        &hotkey_manager,
+                        palette_hotkey,
+                        &new_config.palette_hotkey,
+                        &strings,
+                        &notifier,
+                    );
+                    palette_hotkey_str = new_config.palette_hotkey;
+                }
 
                 // Update State
                 locale = new_locale;
-                app_entries = new_app_entries;
-                menu = new_menu;
-                app_map = new_map;
+                app_entries = new_config.app_entries;
+                app_menu = new_menu;
+                refresh_running_menu(&app_menu.running, &mut registry, &strings);
 
-                // Update Tray Menu
-                let _ = tray_icon.set_menu(Some(Box::new(menu.clone())));
+                tray_clicks = new_config.tray_clicks;
+                editor = new_config.editor;
                 log_msg("INFO", "Configuration Reloaded.");
-            } else if id == MENU_ID_EXIT {
+            }
+            Action::Exit => {
                 log_msg("INFO", "Exiting Application.");
+                logging::flush();
                 *control_flow = ControlFlow::Exit;
-            } else if let Some(cmd) = app_map.get(id) {
-                log_msg("INFO", &format!("Executing Command: {}", cmd));
-                let parts = parse_cmd(cmd);
-                if !parts.is_empty() {
-                    let res = if parts.len() > 1 {
-                        // Multiple parts: Execute as Command (Exe + Args)
-                        std::process::Command::new(&parts[0])
-                            .args(&parts[1..])
-                            .spawn()
-                            .map(|_| ())
-                            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
-                    } else {
-                        // Single part: Use open::that (Supports URLs, Files, Folders)
-                        open::that(&parts[0])
-                    };
-
-                    if let Err(e) = res {
-                        let err_msg = format!("Execution Failed: {}", e);
-                        eprintln!("{}", err_msg);
-                        log_msg("ERROR", &err_msg);
-                    } else {
-                        log_msg("INFO", "Execution Triggered Successfully.");
-                    }
+            }
+            Action::FocusProcess(key) => {
+                let pid = registry.get(key).map(|p| p.pid);
+                let window = pid.and_then(|pid| {
+                    focus::top_level_windows()
+                        .into_iter()
+                        .find(|(_, info)| info.pid == pid)
+                });
+                match window {
+                    Some((hwnd, _)) => focus::activate_window(hwnd),
+                    None => log_msg("WARN", "No Window Found for Process."),
+                }
+            }
+            Action::TerminateProcess(key) => {
+                match registry.terminate(key) {
+                    Ok(()) => log_msg("INFO", "Process Terminated."),
+                    Err(e) => log_msg("ERROR", &format!("Terminate Failed: {}", e)),
                 }
+                refresh_running_menu(&app_menu.running, &mut registry, &strings);
+            }
+            Action::RunItem(id) => {
+                let Some(entry) = app_menu.app_map.get(&id) else {
+                    log_msg("WARN", &format!("Unknown Item: {}", id));
+                    return;
+                };
+                let launched = run_entry(
+                    entry,
+                    &strings,
+                    &notifier,
+                    &capture_tx,
+                    &mut registry,
+                    dry_run,
+                );
+                if let Some(success) = launched {
+                    record_history(&mut history, &history_settings, entry, success);
+                }
+                refresh_recent_menu(&app_menu, &history, &history_settings);
+                refresh_running_menu(&app_menu.running, &mut registry, &strings);
+            }
+            Action::ShowPalette => {
+                log_msg("INFO", "Palette Hotkey Pressed.");
+                let counts = if history_settings.enabled {
+                    history.counts()
+                } else {
+                    HashMap::new()
+                };
+                palette::show(&app_entries, &locale, counts);
+            }
+            Action::ShowMenu => {
+                refresh_running_menu(&app_menu.running, &mut registry, &strings);
+                show_popup_menu(&window, &app_menu.menu);
+            }
+            Action::ShowAdminMenu => {
+                // Nothing to show when [global] builtins=none
+                if !app_menu.admin.items().is_empty() {
+                    show_popup_menu(&window, &app_menu.admin);
+                }
+            }
+            Action::StartClickTimer(serial) => {
+                // Wait out a possible second click before the left-click action runs
+                let delay = std::time::Duration::from_millis(unsafe { GetDoubleClickTime() } as u64);
+                let proxy = timer_proxy.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(delay);
+                    let _ = proxy.send_event(UserEvent::TrayClickElapsed(serial));
+                });
+            }
+            Action::RefreshRunning => {
+                refresh_running_menu(&app_menu.running, &mut registry, &strings);
+            }
+            Action::ReportCapture(report) => {
+                registry.mark_exited(report.pid);
+                refresh_running_menu(&app_menu.running, &mut registry, &strings);
+                report_capture(&report, &strings, &notifier);
+            }
+            Action::UnknownMenuItem(id) => {
+                log_msg("WARN", &format!("Unknown Menu Item: {}", id));
             }
         }
+    });
+}
 
-        if let Ok(event) = tray_channel.try_recv() {
-            // println!("{event:?}");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> LocalizedStrings {
+        LocalizedStrings::load("en", Path::new("no-such-locales-dir"))
+    }
+
+    fn capture_report(exit_code: Option<i32>, stdout: &str, stderr: &str) -> CaptureReport {
+        CaptureReport {
+            label: "Build".to_string(),
+            pid: 42,
+            exit_code,
+            duration: std::time::Duration::from_secs(3),
+            stdout_tail: stdout.to_string(),
+            stderr_tail: stderr.to_string(),
         }
+    }
 
-        if let Ok(event) = hotkey_channel.try_recv() {
-            if event.state == global_hotkey::HotKeyState::Pressed {
-                if let Some(hk) = current_hotkey {
-                    if event.id == hk.id() {
-                        log_msg("INFO", "Valid Hotkey Pressed. Processing...");
+    #[test]
+    fn report_capture_notifies_failure_with_stderr_tail() {
+        let notifier = RecordingNotifier::default();
+        let report = capture_report(Some(2), "building", "a\nb\nc\nerror: failed");
+        report_capture(&report, &english(), &notifier);
+        let sent = notifier.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, Severity::Error);
+        assert_eq!(sent[0].1, "Build");
+        assert_eq!(sent[0].2, "Build exited with code 2.\nb\nc\nerror: failed");
+    }
 
-                        // Drain multiple clicks
-                        while let Ok(_) = hotkey_channel.try_recv() {}
+    #[test]
+    fn report_capture_uses_stdout_without_stderr() {
+        let notifier = RecordingNotifier::default();
+        report_capture(&capture_report(None, "killed", ""), &english(), &notifier);
+        assert_eq!(notifier.sent.borrow()[0].2, "Build exited with code none.\nkilled");
+    }
 
-                        // Show context menu at cursor
-                        unsafe {
-                            window.set_visible(true);
+    #[test]
+    fn report_capture_is_quiet_on_success() {
+        let notifier = RecordingNotifier::default();
+        report_capture(&capture_report(Some(0), "done", "warning"), &english(), &notifier);
+        assert!(notifier.sent.borrow().is_empty());
+    }
 
-                            let hwnd = HWND(window.hwnd() as _);
-                            force_window_foreground(hwnd);
+    #[test]
+    fn report_diagnostics_notifies_once_with_first_problem() {
+        let notifier = RecordingNotifier::default();
+        let diagnostics = vec![
+            Diagnostic::new(7, "diag_item_without_app", &["Missing"]),
+            Diagnostic::new(9, "diag_duplicate_id", &["cmd", "4"]),
+        ];
+        report_diagnostics(&diagnostics, &english(), &notifier);
+
+        let sent = notifier.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, Severity::Warning);
+        assert_eq!(
+            sent[0].2,
+            "2 problem(s) in config. Line 7: Item section without app: [item:Missing]"
+        );
+    }
 
-                            // Reset any stuck menu state (e.g. from Alt key)
-                            SendMessageW(hwnd, WM_CANCELMODE, WPARAM(0), LPARAM(0));
+    #[test]
+    fn report_diagnostics_is_quiet_without_problems() {
+        let notifier = RecordingNotifier::default();
+        report_diagnostics(&[], &english(), &notifier);
+        assert!(notifier.sent.borrow().is_empty());
+    }
 
-                            log_msg("INFO", "Showing Menu...");
-                            let _ = menu.show_context_menu_for_hwnd(window.hwnd() as isize, None);
-                            log_msg("INFO", "Menu Closed (Event Loop Resuming)");
+    #[test]
+    fn register_hotkey_failure_notifies() {
+        let strings = english();
+        let hotkey = "[Ctrl]+[Alt]+[Shift]+[F11]";
+        // Hold the combination with one manager so the second registration fails
+        let holder = GlobalHotKeyManager::new().unwrap();
+        let _ = holder.register(parse_hotkey(hotkey).unwrap());
+
+        let manager = GlobalHotKeyManager::new().unwrap();
+        let notifier = RecordingNotifier::default();
+        assert_eq!(register_hotkey(&manager, None, hotkey, &strings, &notifier), None);
+
+        let sent = notifier.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, Severity::Error);
+        assert_eq!(sent[0].2, format!("Failed to register hotkey {}.", hotkey));
+    }
 
-                            window.set_visible(false);
+    #[test]
+    fn dry_run_notifies_and_starts_nothing() {
+        let entry = AppEntry {
+            id: "cmd".to_string(),
+            path: Vec::new(),
+            label: "CMD".to_string(),
+            command: "cmd /k echo".to_string(),
+            options: Default::default(),
+            line: 1,
+        };
+        let notifier = RecordingNotifier::default();
+        let (capture_tx, _capture_rx) = std::sync::mpsc::channel();
+        let mut registry = ProcessRegistry::default();
+
+        let result = run_entry(&entry, &english(), &notifier, &capture_tx, &mut registry, true);
+        assert_eq!(result, None);
+        assert!(registry.list().is_empty());
+        let sent = notifier.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].2.contains(r#"program="cmd" args=["/k", "echo"]"#), "{}", sent[0].2);
+    }
 
-                            // Ensure we release focus/foreground cleanly (optional, but good practice)
-                            // SetForegroundWindow(GetDesktopWindow());
-                        }
-                    }
-                } else {
-                    log_msg("WARN", "Hotkey Pressed but ID mismatch or unknown");
-                }
-            }
-        }
-    });
+    #[test]
+    fn register_hotkey_ignores_empty_setting() {
+        let manager = GlobalHotKeyManager::new().unwrap();
+        let notifier = RecordingNotifier::default();
+        assert_eq!(register_hotkey(&manager, None, "", &english(), &notifier), None);
+        assert!(notifier.sent.borrow().is_empty());
+    }
 }
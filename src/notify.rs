@@ -0,0 +1,289 @@
+use std::cell::RefCell;
+
+use windows::{
+    core::{w, PCWSTR},
+    Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+    Win32::System::LibraryLoader::GetModuleHandleW,
+    Win32::UI::Shell::{
+        Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_ERROR, NIIF_INFO,
+        NIIF_WARNING, NIM_ADD, NIM_DELETE, NIM_MODIFY, NIN_BALLOONHIDE, NIN_BALLOONTIMEOUT,
+        NIN_BALLOONUSERCLICK, NOTIFYICONDATAW, NOTIFY_ICON_INFOTIP_FLAGS,
+    },
+    Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, LoadIconW, RegisterClassW, HICON, HMENU,
+        HWND_MESSAGE, IDI_APPLICATION, WINDOW_EX_STYLE, WINDOW_STYLE, WM_APP, WNDCLASSW,
+    },
+};
+
+// QikMenu's own balloon icon, separate from the tray-icon crate's menu icon.
+// It is added for a notification and removed when the balloon closes.
+const BALLOON_CLASS: PCWSTR = w!("QikMenuBalloon");
+const BALLOON_UID: u32 = 1;
+const WM_BALLOON: u32 = WM_APP + 1;
+// Executable icon compiled in by build.rs (winres uses resource ID 1), as MAKEINTRESOURCE(1)
+const APP_ICON_ID: PCWSTR = PCWSTR(std::ptr::without_provenance(1));
+
+// Notification Severity | 알림 심각도
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    // Parse a `[global] notify=` level. Returns Some(None) for "off".
+    pub fn parse_level(value: &str) -> Option<Option<Severity>> {
+        match value.trim().to_lowercase().as_str() {
+            "off" | "none" => Some(None),
+            "info" => Some(Some(Severity::Info)),
+            "warn" | "warning" => Some(Some(Severity::Warning)),
+            "error" => Some(Some(Severity::Error)),
+            _ => None,
+        }
+    }
+}
+
+// Notifier Trait | 알림 인터페이스
+pub trait Notifier {
+    fn notify(&self, severity: Severity, title: &str, message: &str);
+
+    // Shown whatever `notify=` is set to, for output the user asked for (dry runs)
+    fn notify_always(&self, severity: Severity, title: &str, message: &str) {
+        self.notify(severity, title, message);
+    }
+}
+
+// Tray Balloon Notifier | 트레이 풍선 알림
+// Shows notifications at or above `min_severity`; None disables them.
+pub struct TrayNotifier {
+    pub min_severity: Option<Severity>,
+    balloon: Option<BalloonIcon>, // None if the window could not be created
+}
+
+impl TrayNotifier {
+    // Creates the balloon window; call on the event loop thread
+    pub fn new(min_severity: Option<Severity>) -> Self {
+        let balloon = match unsafe { BalloonIcon::create() } {
+            Ok(balloon) => Some(balloon),
+            Err(e) => {
+                crate::log_msg("ERROR", &format!("Failed to create notification window: {}", e));
+                None
+            }
+        };
+        Self {
+            min_severity,
+            balloon,
+        }
+    }
+
+    // Whether a notification of `severity` passes the threshold
+    pub fn shows(&self, severity: Severity) -> bool {
+        self.min_severity.is_some_and(|min| severity >= min)
+    }
+}
+
+impl Notifier for TrayNotifier {
+    fn notify(&self, severity: Severity, title: &str, message: &str) {
+        if self.shows(severity) {
+            self.notify_always(severity, title, message);
+        }
+    }
+
+    fn notify_always(&self, severity: Severity, title: &str, message: &str) {
+        let flags = match severity {
+            Severity::Info => NIIF_INFO,
+            Severity::Warning => NIIF_WARNING,
+            Severity::Error => NIIF_ERROR,
+        };
+        let shown = self
+            .balloon
+            .as_ref()
+            .is_some_and(|b| b.show(flags, title, message));
+        if !shown {
+            crate::log_msg("WARN", "Tray notification could not be shown.");
+        }
+    }
+}
+
+// Recording Notifier | 알림 기록기
+// Keeps notifications in memory, e.g. until the tray icon exists.
+#[derive(Default)]
+pub struct RecordingNotifier {
+    pub sent: RefCell<Vec<(Severity, String, String)>>,
+}
+
+impl RecordingNotifier {
+    // Forward everything recorded so far to another notifier
+    pub fn replay(&self, target: &dyn Notifier) {
+        for (severity, title, message) in self.sent.borrow_mut().drain(..) {
+            target.notify(severity, &title, &message);
+        }
+    }
+}
+
+impl Notifier for RecordingNotifier {
+    fn notify(&self, severity: Severity, title: &str, message: &str) {
+        self.sent
+            .borrow_mut()
+            .push((severity, title.to_string(), message.to_string()));
+    }
+}
+
+// Balloon Notify Icon | 풍선 알림 아이콘
+// A message-only window that owns one notify icon (BALLOON_UID)
+struct BalloonIcon {
+    hwnd: HWND,
+}
+
+impl BalloonIcon {
+    unsafe fn create() -> windows::core::Result<Self> {
+        let instance = GetModuleHandleW(None)?;
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(balloon_proc),
+            hInstance: instance.into(),
+            lpszClassName: BALLOON_CLASS,
+            ..Default::default()
+        };
+        RegisterClassW(&class);
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            BALLOON_CLASS,
+            w!("QikMenu"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            HMENU::default(),
+            instance,
+            None,
+        )?;
+        Ok(Self { hwnd })
+    }
+
+    // Function: Show Balloon | 풍선 알림 표시
+    // Adds the icon if it is not there yet; returns false if the shell refused
+    fn show(&self, flags: NOTIFY_ICON_INFOTIP_FLAGS, title: &str, message: &str) -> bool {
+        let mut data = icon_data(self.hwnd);
+        data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP | NIF_INFO;
+        data.uCallbackMessage = WM_BALLOON;
+        data.hIcon = app_icon();
+        data.dwInfoFlags = flags;
+        copy_wide(&mut data.szTip, "QikMenu");
+        copy_wide(&mut data.szInfoTitle, title);
+        copy_wide(&mut data.szInfo, message);
+        unsafe {
+            Shell_NotifyIconW(NIM_MODIFY, &data).as_bool()
+                || Shell_NotifyIconW(NIM_ADD, &data).as_bool()
+        }
+    }
+}
+
+impl Drop for BalloonIcon {
+    fn drop(&mut self) {
+        unsafe {
+            remove_icon(self.hwnd);
+            let _ = DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+fn icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: BALLOON_UID,
+        ..Default::default()
+    }
+}
+
+fn app_icon() -> HICON {
+    unsafe {
+        GetModuleHandleW(None)
+            .and_then(|module| LoadIconW(module, APP_ICON_ID))
+            .or_else(|_| LoadIconW(None, IDI_APPLICATION))
+            .unwrap_or_default()
+    }
+}
+
+unsafe fn remove_icon(hwnd: HWND) {
+    let _ = Shell_NotifyIconW(NIM_DELETE, &icon_data(hwnd));
+}
+
+// The icon only exists for the balloon, so it goes away with it
+unsafe extern "system" fn balloon_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_BALLOON {
+        let event = lparam.0 as u32;
+        if event == NIN_BALLOONHIDE || event == NIN_BALLOONTIMEOUT || event == NIN_BALLOONUSERCLICK
+        {
+            remove_icon(hwnd);
+        }
+        return LRESULT(0);
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+// Copy text into a fixed UTF-16 buffer, truncating and NUL-terminating
+fn copy_wide<const N: usize>(dest: &mut [u16; N], text: &str) {
+    let wide: Vec<u16> = text.encode_utf16().take(N - 1).collect();
+    dest[..wide.len()].copy_from_slice(&wide);
+    dest[wide.len()] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tray_notifier_threshold() {
+        let warning = TrayNotifier {
+            min_severity: Some(Severity::Warning),
+            balloon: None,
+        };
+        assert!(!warning.shows(Severity::Info));
+        assert!(warning.shows(Severity::Warning));
+        assert!(warning.shows(Severity::Error));
+
+        let info = TrayNotifier {
+            min_severity: Some(Severity::Info),
+            balloon: None,
+        };
+        assert!(info.shows(Severity::Info));
+
+        let off = TrayNotifier {
+            min_severity: None,
+            balloon: None,
+        };
+        assert!(!off.shows(Severity::Error));
+    }
+
+    #[test]
+    fn parse_level_values() {
+        assert_eq!(Severity::parse_level("off"), Some(None));
+        assert_eq!(Severity::parse_level(" Warn "), Some(Some(Severity::Warning)));
+        assert_eq!(Severity::parse_level("error"), Some(Some(Severity::Error)));
+        assert_eq!(Severity::parse_level("loud"), None);
+    }
+
+    #[test]
+    fn recording_notifier_replays_in_order() {
+        let first = RecordingNotifier::default();
+        first.notify(Severity::Warning, "QikMenu", "one");
+        first.notify(Severity::Error, "QikMenu", "two");
+
+        let second = RecordingNotifier::default();
+        first.replay(&second);
+        assert!(first.sent.borrow().is_empty());
+        let sent = second.sent.borrow();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0], (Severity::Warning, "QikMenu".to_string(), "one".to_string()));
+        assert_eq!(sent[1].2, "two");
+    }
+}
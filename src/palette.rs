@@ -0,0 +1,337 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use windows::{
+    core::*,
+    Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+    Win32::Graphics::Gdi::{GetStockObject, COLOR_WINDOW, DEFAULT_GUI_FONT, HBRUSH},
+    Win32::System::LibraryLoader::GetModuleHandleW,
+    Win32::UI::Input::KeyboardAndMouse::{SetFocus, VK_DOWN, VK_ESCAPE, VK_RETURN, VK_UP},
+    Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass},
+    Win32::UI::WindowsAndMessaging::*,
+};
+
+use crate::config::AppEntry;
+use crate::fuzzy;
+
+// Palette Geometry | 팔레트 크기
+const PALETTE_WIDTH: i32 = 480;
+const EDIT_HEIGHT: i32 = 26;
+const LIST_HEIGHT: i32 = 240;
+const PADDING: i32 = 6;
+const CLASS_NAME: PCWSTR = w!("QikMenuPalette");
+
+// Searchable Item | 검색 대상 항목
+struct PaletteItem {
+    id: String,
+    text: String, // Localized qualified label, e.g. "Dev Tools/Git"
+}
+
+// Palette Window State | 팔레트 창 상태
+struct Palette {
+    hwnd: HWND,
+    edit: HWND,
+    list: HWND,
+    items: Vec<PaletteItem>,
+    counts: HashMap<String, usize>, // Launch counts used to break ties
+    shown: Vec<usize>,              // Item indexes in list order
+}
+
+// Receives the ID of the chosen item | 선택 항목 ID 수신
+type SelectionHandler = Box<dyn Fn(String)>;
+
+thread_local! {
+    // Window procedures run on the event loop thread, so thread-local state is enough
+    static PALETTE: RefCell<Option<Palette>> = const { RefCell::new(None) };
+    static ON_SELECT: RefCell<Option<SelectionHandler>> = const { RefCell::new(None) };
+}
+
+// Function: Show Palette | 팔레트 표시
+// Creates the window on first use, then resets the query and lists every item
+pub fn show(entries: &[AppEntry], locale: &str, counts: HashMap<String, usize>) {
+    let exists = PALETTE.with(|p| p.borrow().is_some());
+    if !exists {
+        match unsafe { create_window() } {
+            Ok(palette) => PALETTE.with(|p| *p.borrow_mut() = Some(palette)),
+            Err(e) => {
+                crate::log_msg("ERROR", &format!("Failed to create palette: {}", e));
+                return;
+            }
+        }
+    }
+
+    let Some((hwnd, edit)) = PALETTE.with(|p| {
+        let mut p = p.borrow_mut();
+        let palette = p.as_mut()?;
+        palette.items = entries
+            .iter()
+            .map(|e| PaletteItem {
+                id: e.id.clone(),
+                text: e.qualified_label_for(locale),
+            })
+            .collect();
+        palette.counts = counts;
+        Some((palette.hwnd, palette.edit))
+    }) else {
+        return;
+    };
+
+    unsafe {
+        let _ = SetWindowTextW(edit, w!(""));
+        refilter(); // Items may have changed even if the query was already empty
+        center_on_cursor_monitor(hwnd);
+        let _ = ShowWindow(hwnd, SW_SHOW);
+        crate::force_window_foreground(hwnd);
+        let _ = SetFocus(edit);
+    }
+}
+
+// Function: Set Selection Handler | 선택 처리기 설정
+// Called with the item ID chosen with Enter or double-click
+pub fn set_selection_handler(handler: impl Fn(String) + 'static) {
+    ON_SELECT.with(|h| *h.borrow_mut() = Some(Box::new(handler)));
+}
+
+fn hide() {
+    if let Some(hwnd) = PALETTE.with(|p| p.borrow().as_ref().map(|p| p.hwnd)) {
+        unsafe {
+            let _ = ShowWindow(hwnd, SW_HIDE);
+        }
+    }
+}
+
+unsafe fn create_window() -> Result<Palette> {
+    let instance = GetModuleHandleW(None)?;
+    let class = WNDCLASSW {
+        lpfnWndProc: Some(palette_proc),
+        hInstance: instance.into(),
+        lpszClassName: CLASS_NAME,
+        hCursor: LoadCursorW(None, IDC_ARROW)?,
+        hbrBackground: HBRUSH((COLOR_WINDOW.0 + 1) as _),
+        ..Default::default()
+    };
+    RegisterClassW(&class);
+
+    let height = EDIT_HEIGHT + LIST_HEIGHT + PADDING * 3;
+    let hwnd = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
+        CLASS_NAME,
+        w!("QikMenu"),
+        WS_POPUP | WS_BORDER,
+        0,
+        0,
+        PALETTE_WIDTH,
+        height,
+        HWND::default(),
+        HMENU::default(),
+        instance,
+        None,
+    )?;
+    let edit = CreateWindowExW(
+        WS_EX_CLIENTEDGE,
+        w!("EDIT"),
+        w!(""),
+        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+        PADDING,
+        PADDING,
+        PALETTE_WIDTH - PADDING * 2,
+        EDIT_HEIGHT,
+        hwnd,
+        HMENU::default(),
+        instance,
+        None,
+    )?;
+    let list = CreateWindowExW(
+        WINDOW_EX_STYLE::default(),
+        w!("LISTBOX"),
+        w!(""),
+        WS_CHILD | WS_VISIBLE | WS_VSCROLL | WINDOW_STYLE(LBS_NOTIFY as u32),
+        PADDING,
+        PADDING * 2 + EDIT_HEIGHT,
+        PALETTE_WIDTH - PADDING * 2,
+        LIST_HEIGHT,
+        hwnd,
+        HMENU::default(),
+        instance,
+        None,
+    )?;
+
+    let font = GetStockObject(DEFAULT_GUI_FONT);
+    for child in [edit, list] {
+        SendMessageW(child, WM_SETFONT, WPARAM(font.0 as usize), LPARAM(1));
+    }
+    // Keyboard navigation while the caret stays in the edit box
+    let _ = SetWindowSubclass(edit, Some(edit_proc), 1, 0);
+
+    Ok(Palette {
+        hwnd,
+        edit,
+        list,
+        items: Vec::new(),
+        counts: HashMap::new(),
+        shown: Vec::new(),
+    })
+}
+
+// Place the palette in the upper third of the monitor under the mouse cursor
+unsafe fn center_on_cursor_monitor(hwnd: HWND) {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::Graphics::Gdi::{
+        GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTOPRIMARY,
+    };
+
+    let mut cursor = POINT::default();
+    let _ = GetCursorPos(&mut cursor);
+    let monitor = MonitorFromPoint(cursor, MONITOR_DEFAULTTOPRIMARY);
+    let mut info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+        return;
+    }
+    let work = info.rcWork;
+    let x = work.left + (work.right - work.left - PALETTE_WIDTH) / 2;
+    let y = work.top + (work.bottom - work.top) / 4;
+    let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, 0, 0, SWP_NOSIZE);
+}
+
+// Function: Refilter List | 목록 다시 거르기
+unsafe fn refilter() {
+    let Some((edit, list)) = PALETTE.with(|p| p.borrow().as_ref().map(|p| (p.edit, p.list)))
+    else {
+        return;
+    };
+
+    let mut buf = [0u16; 256];
+    let len = GetWindowTextW(edit, &mut buf).max(0) as usize;
+    let query = String::from_utf16_lossy(&buf[..len]);
+
+    // Rank with the state borrowed, then update the list box without it
+    let labels: Vec<String> = PALETTE.with(|p| {
+        let mut p = p.borrow_mut();
+        let Some(palette) = p.as_mut() else {
+            return Vec::new();
+        };
+        let candidates: Vec<(&str, &str)> = palette
+            .items
+            .iter()
+            .map(|i| (i.id.as_str(), i.text.as_str()))
+            .collect();
+        palette.shown = fuzzy::rank(&query, &candidates, &palette.counts);
+        palette
+            .shown
+            .iter()
+            .map(|&i| palette.items[i].text.clone())
+            .collect()
+    });
+
+    SendMessageW(list, LB_RESETCONTENT, WPARAM(0), LPARAM(0));
+    for label in labels {
+        let wide = HSTRING::from(label);
+        SendMessageW(list, LB_ADDSTRING, WPARAM(0), LPARAM(wide.as_ptr() as isize));
+    }
+    SendMessageW(list, LB_SETCURSEL, WPARAM(0), LPARAM(0));
+}
+
+// Move the list selection by `delta`, clamped to the list
+unsafe fn move_selection(delta: i32) {
+    let Some(list) = PALETTE.with(|p| p.borrow().as_ref().map(|p| p.list)) else {
+        return;
+    };
+    let count = SendMessageW(list, LB_GETCOUNT, WPARAM(0), LPARAM(0)).0 as i32;
+    if count <= 0 {
+        return;
+    }
+    let current = SendMessageW(list, LB_GETCURSEL, WPARAM(0), LPARAM(0)).0 as i32;
+    let next = (current + delta).clamp(0, count - 1);
+    SendMessageW(list, LB_SETCURSEL, WPARAM(next as usize), LPARAM(0));
+}
+
+// Function: Accept Selection | 선택 확정
+unsafe fn accept() {
+    let Some(list) = PALETTE.with(|p| p.borrow().as_ref().map(|p| p.list)) else {
+        return;
+    };
+    let current = SendMessageW(list, LB_GETCURSEL, WPARAM(0), LPARAM(0)).0 as i32;
+    let id = PALETTE.with(|p| {
+        let p = p.borrow();
+        let palette = p.as_ref()?;
+        let index = *palette.shown.get(usize::try_from(current).ok()?)?;
+        Some(palette.items[index].id.clone())
+    });
+    if let Some(id) = id {
+        hide();
+        ON_SELECT.with(|h| {
+            if let Some(handler) = h.borrow().as_ref() {
+                handler(id);
+            }
+        });
+    }
+}
+
+unsafe extern "system" fn palette_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_COMMAND => {
+            let code = ((wparam.0 >> 16) & 0xFFFF) as u32;
+            let is_list = PALETTE.with(|p| {
+                p.borrow()
+                    .as_ref()
+                    .is_some_and(|p| p.list.0 as isize == lparam.0)
+            });
+            if is_list && code == LBN_DBLCLK {
+                accept();
+            } else if !is_list && code == EN_CHANGE {
+                refilter();
+            }
+            LRESULT(0)
+        }
+        // Clicking elsewhere dismisses the palette
+        WM_ACTIVATE if (wparam.0 & 0xFFFF) as u32 == WA_INACTIVE => {
+            hide();
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            hide();
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+unsafe extern "system" fn edit_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    _data: usize,
+) -> LRESULT {
+    let key = wparam.0 as u16;
+    match msg {
+        WM_KEYDOWN if key == VK_RETURN.0 => {
+            accept();
+            LRESULT(0)
+        }
+        WM_KEYDOWN if key == VK_ESCAPE.0 => {
+            hide();
+            LRESULT(0)
+        }
+        WM_KEYDOWN if key == VK_UP.0 => {
+            move_selection(-1);
+            LRESULT(0)
+        }
+        WM_KEYDOWN if key == VK_DOWN.0 => {
+            move_selection(1);
+            LRESULT(0)
+        }
+        // Swallow the matching WM_CHAR so the edit box does not beep
+        WM_CHAR if key == 0x0D || key == 0x1B => LRESULT(0),
+        _ => DefSubclassProc(hwnd, msg, wparam, lparam),
+    }
+}
@@ -0,0 +1,253 @@
+use std::io;
+use std::process::Child;
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_TIMEOUT};
+use windows::Win32::System::Threading::{
+    GetProcessId, OpenProcess, TerminateProcess, WaitForSingleObject, PROCESS_TERMINATE,
+};
+
+// Process Handle | 프로세스 핸들
+// A spawned child, or a process started through ShellExecuteEx (open, runas)
+pub enum ProcessHandle {
+    Child(Child),
+    Shell(ShellProcess),
+}
+
+impl ProcessHandle {
+    pub fn id(&self) -> u32 {
+        match self {
+            ProcessHandle::Child(child) => child.id(),
+            ProcessHandle::Shell(process) => process.pid,
+        }
+    }
+
+    fn has_exited(&mut self) -> bool {
+        match self {
+            ProcessHandle::Child(child) => !matches!(child.try_wait(), Ok(None)),
+            ProcessHandle::Shell(process) => process.has_exited(),
+        }
+    }
+
+    fn kill(&mut self) -> io::Result<()> {
+        match self {
+            ProcessHandle::Child(child) => child.kill(),
+            ProcessHandle::Shell(process) => process.kill(),
+        }
+    }
+}
+
+// Process Handle from ShellExecuteEx | ShellExecuteEx 프로세스 핸들
+// Owns the handle and closes it when dropped
+pub struct ShellProcess {
+    handle: HANDLE,
+    pid: u32,
+}
+
+impl ShellProcess {
+    // Takes ownership of `handle` (SEE_MASK_NOCLOSEPROCESS)
+    pub fn from_handle(handle: HANDLE) -> Self {
+        let pid = unsafe { GetProcessId(handle) };
+        Self { handle, pid }
+    }
+
+    fn has_exited(&self) -> bool {
+        unsafe { WaitForSingleObject(self.handle, 0) != WAIT_TIMEOUT }
+    }
+
+    fn kill(&self) -> io::Result<()> {
+        unsafe { TerminateProcess(self.handle, 1) }.map_err(io::Error::other)
+    }
+}
+
+impl Drop for ShellProcess {
+    fn drop(&mut self) {
+        let _ = unsafe { CloseHandle(self.handle) };
+    }
+}
+
+// Process launched by QikMenu | QikMenu가 실행한 프로세스
+pub struct TrackedProcess {
+    pub key: u64, // Stable within this session, used in menu IDs
+    pub label: String,
+    pub pid: u32,
+    pub started: Instant,
+    handle: Option<ProcessHandle>, // None when another owner waits on it (capture mode)
+    exited: bool,
+}
+
+impl TrackedProcess {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+// Process Registry | 프로세스 목록
+#[derive(Default)]
+pub struct ProcessRegistry {
+    next_key: u64,
+    processes: Vec<TrackedProcess>,
+}
+
+impl ProcessRegistry {
+    // Track a new process. Pass `handle` if the registry should own it.
+    pub fn register(&mut self, label: &str, pid: u32, handle: Option<ProcessHandle>) -> u64 {
+        self.next_key += 1;
+        self.processes.push(TrackedProcess {
+            key: self.next_key,
+            label: label.to_string(),
+            pid,
+            started: Instant::now(),
+            handle,
+            exited: false,
+        });
+        self.next_key
+    }
+
+    // Mark a process finished by an outside owner (e.g. capture thread)
+    pub fn mark_exited(&mut self, pid: u32) {
+        for p in self.processes.iter_mut().filter(|p| p.pid == pid) {
+            p.exited = true;
+        }
+    }
+
+    // Remove finished processes and return their labels
+    pub fn prune(&mut self) -> Vec<String> {
+        for p in self.processes.iter_mut() {
+            if p.handle.as_mut().is_some_and(ProcessHandle::has_exited) {
+                p.exited = true;
+            }
+        }
+        let (finished, running): (Vec<_>, Vec<_>) =
+            self.processes.drain(..).partition(|p| p.exited);
+        self.processes = running;
+        finished.into_iter().map(|p| p.label).collect()
+    }
+
+    pub fn list(&self) -> &[TrackedProcess] {
+        &self.processes
+    }
+
+    pub fn get(&self, key: u64) -> Option<&TrackedProcess> {
+        self.processes.iter().find(|p| p.key == key)
+    }
+
+    // Function: Terminate Process | 프로세스 종료
+    pub fn terminate(&mut self, key: u64) -> io::Result<()> {
+        let Some(p) = self.processes.iter_mut().find(|p| p.key == key) else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "Process not tracked"));
+        };
+        match p.handle.as_mut() {
+            Some(handle) => handle.kill()?,
+            None => terminate_pid(p.pid)?,
+        }
+        p.exited = true;
+        Ok(())
+    }
+}
+
+// Terminate by PID when the handle is owned elsewhere
+fn terminate_pid(pid: u32) -> io::Result<()> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, false, pid).map_err(io::Error::other)?;
+        let res = TerminateProcess(handle, 1);
+        let _ = CloseHandle(handle);
+        res.map_err(io::Error::other)
+    }
+}
+
+// Function: Format Uptime | 실행 시간 표시
+// 42s, 3m 12s, 2h 05m
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use std::thread;
+
+    fn labels(registry: &ProcessRegistry) -> Vec<&str> {
+        registry.list().iter().map(|p| p.label.as_str()).collect()
+    }
+
+    #[test]
+    fn register_assigns_increasing_keys() {
+        let mut registry = ProcessRegistry::default();
+        let first = registry.register("A", 10, None);
+        let second = registry.register("B", 20, None);
+        assert!(second > first);
+        assert_eq!(registry.get(second).map(|p| p.pid), Some(20));
+        assert!(registry.get(second + 1).is_none());
+    }
+
+    #[test]
+    fn prune_keeps_processes_not_marked_exited() {
+        let mut registry = ProcessRegistry::default();
+        registry.register("A", 10, None);
+        registry.register("B", 20, None);
+        assert!(registry.prune().is_empty());
+        assert_eq!(labels(&registry), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn mark_exited_then_prune_removes_only_that_pid() {
+        let mut registry = ProcessRegistry::default();
+        registry.register("A", 10, None);
+        registry.register("B", 20, None);
+        registry.register("C", 20, None);
+        registry.mark_exited(20);
+        assert_eq!(registry.prune(), vec!["B", "C"]);
+        assert_eq!(labels(&registry), vec!["A"]);
+        assert!(registry.prune().is_empty());
+    }
+
+    #[test]
+    fn mark_exited_unknown_pid_is_ignored() {
+        let mut registry = ProcessRegistry::default();
+        registry.register("A", 10, None);
+        registry.mark_exited(99);
+        assert!(registry.prune().is_empty());
+        assert_eq!(registry.list().len(), 1);
+    }
+
+    #[test]
+    fn prune_detects_exited_child() {
+        let child = Command::new("cmd").args(["/C", "exit", "0"]).spawn().unwrap();
+        let mut registry = ProcessRegistry::default();
+        registry.register("cmd", child.id(), Some(ProcessHandle::Child(child)));
+
+        let mut finished = Vec::new();
+        for _ in 0..100 {
+            finished = registry.prune();
+            if !finished.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert_eq!(finished, vec!["cmd"]);
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn terminate_unknown_key_is_not_found() {
+        let mut registry = ProcessRegistry::default();
+        let err = registry.terminate(1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn format_uptime_units() {
+        assert_eq!(format_uptime(Duration::from_secs(42)), "42s");
+        assert_eq!(format_uptime(Duration::from_secs(192)), "3m 12s");
+        assert_eq!(format_uptime(Duration::from_secs(7500)), "2h 05m");
+    }
+}
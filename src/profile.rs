@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+use crate::cli::CliArgs;
+
+// Default config file name next to the executable | 기본 환경 설정 파일 이름
+const DEFAULT_CONFIG: &str = "QikMenu.ini";
+
+// Running Profile | 실행 프로필
+// Each profile has its own config file, and only one instance per profile may run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub name: Option<String>,
+    pub ini_path: PathBuf,
+}
+
+impl Profile {
+    // Function: Resolve Profile | 프로필 결정
+    // --config wins and, like `validate <ini>`, is relative to the current directory;
+    // --profile work uses QikMenu-work.ini next to the executable
+    pub fn resolve(args: &CliArgs, exe_dir: &Path, current_dir: &Path) -> Self {
+        let name = args
+            .profile
+            .as_deref()
+            .map(sanitize_name)
+            .filter(|n| !n.is_empty());
+        let ini_path = match (&args.config, &name) {
+            (Some(config), _) => current_dir.join(config),
+            (None, Some(name)) => exe_dir.join(format!("QikMenu-{}.ini", name)),
+            (None, None) => exe_dir.join(DEFAULT_CONFIG),
+        };
+        Self { name, ini_path }
+    }
+
+    // Label for logs and the tooltip: the profile name or "default"
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+
+    // Function: Instance Key | 인스턴스 구분 키
+    // The lowercased profile name, or a hash of the config path when none is given.
+    // Windows file names are case-insensitive, so "Work" and "work" share a config
+    pub fn instance_key(&self) -> String {
+        match &self.name {
+            Some(name) => name.to_lowercase(),
+            None => {
+                let path = self
+                    .ini_path
+                    .canonicalize()
+                    .unwrap_or_else(|_| self.ini_path.clone());
+                let normalized = path.to_string_lossy().to_lowercase();
+                format!("{:016x}", fnv1a(normalized.as_bytes()))
+            }
+        }
+    }
+
+    // Name of the single-instance lock
+    pub fn lock_name(&self) -> String {
+        format!("QikMenu_Lock_{}", self.instance_key())
+    }
+}
+
+// Keep names safe for file names and kernel object names
+fn sanitize_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// FNV-1a: stable across builds, unlike std's DefaultHasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(profile: Option<&str>, config: Option<&str>) -> CliArgs {
+        CliArgs {
+            profile: profile.map(String::from),
+            config: config.map(PathBuf::from),
+            command: None,
+            subcommand: None,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn default_config_is_next_to_the_executable() {
+        let profile = Profile::resolve(&args(None, None), Path::new("exe"), Path::new("cwd"));
+        assert_eq!(profile.ini_path, Path::new("exe").join("QikMenu.ini"));
+        assert_eq!(profile.display_name(), "default");
+    }
+
+    #[test]
+    fn profile_config_is_next_to_the_executable() {
+        let profile = Profile::resolve(&args(Some("work"), None), Path::new("exe"), Path::new("cwd"));
+        assert_eq!(profile.ini_path, Path::new("exe").join("QikMenu-work.ini"));
+        assert_eq!(profile.lock_name(), "QikMenu_Lock_work");
+    }
+
+    #[test]
+    fn config_is_relative_to_the_current_directory() {
+        let profile = Profile::resolve(
+            &args(Some("work"), Some("foo.ini")),
+            Path::new("exe"),
+            Path::new("cwd"),
+        );
+        assert_eq!(profile.ini_path, Path::new("cwd").join("foo.ini"));
+        assert_eq!(profile.name.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn profile_names_are_sanitized() {
+        let profile = Profile::resolve(&args(Some("a/b c"), None), Path::new("exe"), Path::new("cwd"));
+        assert_eq!(profile.name.as_deref(), Some("a_b_c"));
+        let blank = Profile::resolve(&args(Some("  "), None), Path::new("exe"), Path::new("cwd"));
+        assert_eq!(blank.name, None);
+    }
+
+    #[test]
+    fn instance_key_ignores_profile_case() {
+        let resolve =
+            |name| Profile::resolve(&args(Some(name), None), Path::new("exe"), Path::new("cwd"));
+        let (upper, lower) = (resolve("Work"), resolve("work"));
+        assert_eq!(upper.instance_key(), lower.instance_key());
+        assert_eq!(upper.lock_name(), "QikMenu_Lock_work");
+        assert_eq!(upper.display_name(), "Work");
+    }
+}
@@ -0,0 +1,25 @@
+// Function: Render Template | 템플릿 채우기
+// Replaces {name} with the matching value; unknown names are left as they are
+pub fn render(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut result = template.to_string();
+    for (name, value) in vars {
+        result = result.replace(&format!("{{{}}}", name), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_placeholders_are_kept() {
+        assert_eq!(render("{hotkey} {nope}", &[("hotkey", "Alt+/")]), "Alt+/ {nope}");
+        assert_eq!(render("no placeholders", &[("x", "y")]), "no placeholders");
+    }
+
+    #[test]
+    fn repeated_placeholders_are_all_filled() {
+        assert_eq!(render("{a}-{a}-{b}", &[("a", "1"), ("b", "2")]), "1-1-2");
+    }
+}